@@ -0,0 +1,224 @@
+/*!
+# API Key Authentication
+
+This module provides API key authentication, as used by many SaaS APIs: a static key sent with each request,
+either in a header such as `X-API-Key`, or in a query parameter such as `api_key`.
+
+## Security Note
+
+Query strings are often written to server and proxy logs: send the key in a header when the API allows it,
+and always over HTTPS/TLS. The clients leave the key out of the URLs they log, report in error details and
+return with [`HttpResponse::url`](crate::HttpResponse::url).
+
+## Examples
+
+```rust
+use cdumay_http_client::{ClientBuilder, HttpClient};
+use cdumay_http_client::authentication::api_key::{ApiKeyAuth, ApiKeyLocation};
+
+let client = HttpClient::new("https://api.example.com", None).unwrap()
+    .set_auth(ApiKeyAuth::header("X-API-Key", "your-key"));
+
+let auth = ApiKeyAuth::query("api_key", "your-key");
+let auth = ApiKeyAuth::new("api_key", "your-key", ApiKeyLocation::Query);
+```
+*/
+
+use crate::authentication::Authentication;
+use reqwest::header::{HeaderName, HeaderValue};
+use std::fmt::{Debug, Formatter};
+
+/// Where the API key is sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiKeyLocation {
+    /// In a request header.
+    Header,
+    /// In a query parameter.
+    Query,
+}
+
+/// API key authentication implementation.
+///
+/// The key is never printed by the `Debug` implementation, and the header is marked as sensitive.
+pub struct ApiKeyAuth {
+    name: String,
+    key: String,
+    location: ApiKeyLocation,
+}
+
+impl ApiKeyAuth {
+    /// Creates an API key authentication sending `key` in the header or query parameter `name`.
+    pub fn new<N: Into<String>, K: Into<String>>(name: N, key: K, location: ApiKeyLocation) -> ApiKeyAuth {
+        ApiKeyAuth {
+            name: name.into(),
+            key: key.into(),
+            location,
+        }
+    }
+
+    /// Creates an API key authentication sending `key` in the header `name`.
+    pub fn header<N: Into<String>, K: Into<String>>(name: N, key: K) -> ApiKeyAuth {
+        ApiKeyAuth::new(name, key, ApiKeyLocation::Header)
+    }
+
+    /// Creates an API key authentication sending `key` in the query parameter `name`.
+    pub fn query<N: Into<String>, K: Into<String>>(name: N, key: K) -> ApiKeyAuth {
+        ApiKeyAuth::new(name, key, ApiKeyLocation::Query)
+    }
+
+    /// Returns the name of the header or query parameter.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns where the key is sent.
+    pub fn location(&self) -> ApiKeyLocation {
+        self.location
+    }
+}
+
+impl Debug for ApiKeyAuth {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApiKeyAuth")
+            .field("name", &self.name)
+            .field("location", &self.location)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Authentication for ApiKeyAuth {
+    fn username(&self) -> Option<String> { None }
+    fn password(&self) -> Option<String> { None }
+
+    /// Generates the API key header, when the key is sent in a header.
+    ///
+    /// Returns `None` when the name or the key is not allowed in a header.
+    fn as_header(&self) -> Option<(HeaderName, HeaderValue)> {
+        match self.location {
+            ApiKeyLocation::Header => {
+                let name = HeaderName::from_bytes(self.name.as_bytes()).ok()?;
+                let mut value = HeaderValue::from_str(&self.key).ok()?;
+                value.set_sensitive(true);
+                Some((name, value))
+            }
+            ApiKeyLocation::Query => None,
+        }
+    }
+
+    /// Returns the API key query parameter, when the key is sent in the query string.
+    fn as_query(&self) -> Vec<(String, String)> {
+        match self.location {
+            ApiKeyLocation::Header => Vec::new(),
+            ApiKeyLocation::Query => vec![(self.name.clone(), self.key.clone())],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use std::net::TcpListener;
+    use reqwest::Method;
+    use wiremock::matchers::{header, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::ApiKeyAuth;
+    use crate::errors::client::NETWORK_CONNECTION;
+    use crate::errors::http::NOT_FOUND;
+    use crate::{AsyncHttpClient, ClientBuilder, HttpClient};
+
+    #[tokio::test]
+    async fn test_api_key_auth() {
+        let server = MockServer::start().await;
+        Mock::given(header("x-api-key", "secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("header"))
+            .mount(&server)
+            .await;
+        Mock::given(query_param("api_key", "secret"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("query"))
+            .mount(&server)
+            .await;
+
+        let cli = AsyncHttpClient::new(&server.uri(), None)
+            .unwrap()
+            .set_auth(ApiKeyAuth::header("X-API-Key", "secret"));
+        assert_eq!(cli.get("/".into(), None, None, None, None, None).await.unwrap(), "header");
+
+        let cli = AsyncHttpClient::new(&server.uri(), None)
+            .unwrap()
+            .set_auth(ApiKeyAuth::query("api_key", "secret"));
+        let params = HashMap::from([("page".to_string(), "2".to_string())]);
+        assert_eq!(cli.get("/".into(), Some(params), None, None, None, None).await.unwrap(), "query");
+
+        assert!(!format!("{:?}", ApiKeyAuth::query("api_key", "secret")).contains("secret"));
+    }
+
+    #[tokio::test]
+    async fn test_api_key_hidden() {
+        let server = MockServer::start().await;
+        Mock::given(path("/users"))
+            .and(query_param("api_key", "secret"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        Mock::given(path("/missing"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let cli = AsyncHttpClient::new(&server.uri(), None)
+            .unwrap()
+            .set_retry_number(1)
+            .set_auth(ApiKeyAuth::query("api_key", "secret"));
+        let response = cli.request(Method::GET, "/users").query("page", "2").execute().await.unwrap();
+        assert_eq!(response.url().as_str(), format!("{}/users?page=2", server.uri()));
+        let response = cli.request(Method::GET, "/users").query("api_key", "mine").execute().await.unwrap();
+        assert_eq!(response.url().as_str(), format!("{}/users?api_key=mine", server.uri()));
+
+        let err = cli.request(Method::GET, "/missing").execute().await.unwrap_err();
+        assert_eq!(err.kind, NOT_FOUND);
+        assert!(!format!("{:?}", err).contains("secret"));
+
+        let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let cli = AsyncHttpClient::new(&format!("http://127.0.0.1:{}", port), None)
+            .unwrap()
+            .set_retry_number(1)
+            .set_auth(ApiKeyAuth::query("api_key", "secret"));
+        let err = cli.request(Method::GET, "/users").execute().await.unwrap_err();
+        assert_eq!(err.kind, NETWORK_CONNECTION);
+        assert!(!format!("{:?}", err).contains("secret"));
+    }
+
+    #[tokio::test]
+    async fn test_api_key_auth_blocking() {
+        let server = MockServer::start().await;
+        Mock::given(path("/header"))
+            .and(header("x-api-key", "secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("header"))
+            .expect(1_u64)
+            .mount(&server)
+            .await;
+        Mock::given(path("/query"))
+            .and(query_param("api_key", "secret"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("query"))
+            .expect(1_u64)
+            .mount(&server)
+            .await;
+
+        let uri = server.uri();
+        let (header, query) = tokio::task::spawn_blocking(move || {
+            let cli = HttpClient::new(&uri, None).unwrap().set_auth(ApiKeyAuth::header("X-API-Key", "secret"));
+            let header = cli.get("/header".into(), None, None, None, None, None).unwrap();
+            let cli = HttpClient::new(&uri, None).unwrap().set_auth(ApiKeyAuth::query("api_key", "secret"));
+            let response = cli.request(Method::GET, "/query").query("page", "2").execute().unwrap();
+            assert_eq!(response.url().as_str(), format!("{}/query?page=2", uri));
+            (header, response.into_text())
+        })
+        .await
+        .unwrap();
+        assert_eq!(header, "header");
+        assert_eq!(query, "query");
+    }
+}
@@ -0,0 +1,214 @@
+/*!
+# Bearer Authentication
+
+This module provides the HTTP Bearer Authentication scheme (RFC 6750), used to send OAuth2 access tokens
+and most API tokens. The client sends the token in the `Authorization` header, after the word `Bearer`.
+
+The token is either static, or returned by a provider closure called for each request, so that it can be
+read from a secret store or renewed without rebuilding the client.
+
+## Security Note
+
+Like Basic Authentication, bearer tokens are sent as is: they should only be used over HTTPS/TLS.
+
+## Examples
+
+### Static Token
+
+```rust
+use cdumay_http_client::{ClientBuilder, HttpClient};
+use cdumay_http_client::authentication::bearer::BearerAuth;
+
+let client = HttpClient::new("https://api.example.com", None).unwrap()
+    .set_auth(BearerAuth::new("your-token"));
+```
+
+### Token Provider
+
+```rust
+use cdumay_http_client::{ClientBuilder, HttpClient};
+use cdumay_http_client::authentication::bearer::BearerAuth;
+
+let auth = BearerAuth::with_provider(|| std::env::var("API_TOKEN").ok());
+
+let client = HttpClient::new("https://api.example.com", None).unwrap()
+    .set_auth(auth);
+```
+*/
+
+use crate::authentication::Authentication;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
+use std::fmt::{Debug, Formatter};
+
+/// Returns the token of a request, or `None` to send it without an `Authorization` header.
+type TokenProvider = dyn Fn() -> Option<String> + Send + Sync;
+
+enum Token {
+    Static(String),
+    Provider(Box<TokenProvider>),
+}
+
+/// Bearer Authentication implementation.
+///
+/// The token is never printed by the `Debug` implementation, and the header is marked as sensitive.
+///
+/// # Examples
+///
+/// ```rust
+/// use cdumay_http_client::authentication::bearer::BearerAuth;
+///
+/// let auth = BearerAuth::new("your-token");
+/// let auth = BearerAuth::with_provider(|| Some("your-token".to_string()));
+/// ```
+pub struct BearerAuth {
+    token: Token,
+}
+
+impl BearerAuth {
+    /// Creates a Bearer Authentication sending the same token with each request.
+    pub fn new<T: Into<String>>(token: T) -> BearerAuth {
+        BearerAuth { token: Token::Static(token.into()) }
+    }
+
+    /// Creates a Bearer Authentication calling `provider` for the token of each request.
+    ///
+    /// Requests are sent without an `Authorization` header when the provider returns `None`.
+    pub fn with_provider<F>(provider: F) -> BearerAuth
+    where
+        F: Fn() -> Option<String> + Send + Sync + 'static,
+    {
+        BearerAuth { token: Token::Provider(Box::new(provider)) }
+    }
+
+    /// Returns the token to send, calling the provider if any.
+    pub fn token(&self) -> Option<String> {
+        match &self.token {
+            Token::Static(token) => Some(token.clone()),
+            Token::Provider(provider) => provider(),
+        }
+    }
+}
+
+impl Debug for BearerAuth {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let token = match self.token {
+            Token::Static(_) => "<static>",
+            Token::Provider(_) => "<provider>",
+        };
+        f.debug_struct("BearerAuth").field("token", &token).finish()
+    }
+}
+
+impl Authentication for BearerAuth {
+    fn username(&self) -> Option<String> { None }
+    fn password(&self) -> Option<String> { None }
+
+    /// Generates the Bearer Authentication header.
+    ///
+    /// Returns `None` when there is no token, or when the token contains characters which are not allowed
+    /// in a header value.
+    fn as_header(&self) -> Option<(HeaderName, HeaderValue)> {
+        let mut value = HeaderValue::from_str(&format!("Bearer {}", self.token()?)).ok()?;
+        value.set_sensitive(true);
+        Some((AUTHORIZATION, value))
+    }
+
+    /// Asks for the request to be sent again when the token comes from a provider, which may return a
+    /// renewed one.
+    fn on_unauthorized(&self, _headers: &HeaderMap) -> bool {
+        matches!(self.token, Token::Provider(_))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use reqwest::header::AUTHORIZATION;
+    use reqwest::Method;
+    use wiremock::matchers::header;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::BearerAuth;
+    use crate::authentication::Authentication;
+    use crate::errors::http::UNAUTHORIZED;
+    use crate::{AsyncHttpClient, ClientBuilder, HttpClient};
+
+    #[tokio::test]
+    async fn test_bearer_auth() {
+        let server = MockServer::start().await;
+        Mock::given(header("authorization", "Bearer token-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("first"))
+            .mount(&server)
+            .await;
+        Mock::given(header("authorization", "Bearer token-2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("second"))
+            .mount(&server)
+            .await;
+
+        let cli = AsyncHttpClient::new(&server.uri(), None).unwrap().set_auth(BearerAuth::new("token-1"));
+        assert_eq!(cli.get("/".into(), None, None, None, None, None).await.unwrap(), "first");
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let auth = BearerAuth::with_provider(move || {
+            Some(format!("token-{}", counter.fetch_add(1, Ordering::SeqCst) + 1))
+        });
+        let cli = AsyncHttpClient::new(&server.uri(), None).unwrap().set_auth(auth);
+        assert_eq!(cli.get("/".into(), None, None, None, None, None).await.unwrap(), "first");
+        assert_eq!(cli.get("/".into(), None, None, None, None, None).await.unwrap(), "second");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        assert!(BearerAuth::with_provider(|| None).as_header().is_none());
+        assert!(BearerAuth::new("invalid\ntoken").as_header().is_none());
+        let (name, value) = BearerAuth::new("secret").as_header().unwrap();
+        assert_eq!(name, AUTHORIZATION);
+        assert!(value.is_sensitive());
+        assert!(!format!("{:?}", BearerAuth::new("secret")).contains("secret"));
+    }
+
+    #[tokio::test]
+    async fn test_bearer_auth_rejected() {
+        let server = MockServer::start().await;
+        Mock::given(header("authorization", "Bearer token"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+
+        let cli = AsyncHttpClient::new(&server.uri(), None)
+            .unwrap()
+            .set_retry_number(5)
+            .set_retry_delay(0)
+            .set_auth(BearerAuth::with_provider(|| Some("token".to_string())));
+        let err = cli.request(Method::GET, "/").execute().await.unwrap_err();
+        assert_eq!(err.kind, UNAUTHORIZED);
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_bearer_auth_blocking() {
+        let server = MockServer::start().await;
+        Mock::given(header("authorization", "Bearer token-1"))
+            .respond_with(ResponseTemplate::new(401))
+            .expect(1_u64)
+            .mount(&server)
+            .await;
+        Mock::given(header("authorization", "Bearer token-2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .expect(1_u64)
+            .mount(&server)
+            .await;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let auth = BearerAuth::with_provider(move || {
+            Some(format!("token-{}", counter.fetch_add(1, Ordering::SeqCst) + 1))
+        });
+        let cli = HttpClient::new(&server.uri(), None).unwrap().set_retry_delay(0).set_auth(auth);
+        let body = tokio::task::spawn_blocking(move || cli.get("/".into(), None, None, None, None, None).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(body, "ok");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,448 @@
+/*!
+# Digest Authentication
+
+This module provides the HTTP Digest Authentication scheme (RFC 7616), still required by many network
+appliances. Unlike Basic Authentication, the password is never sent: the server answers the first request
+with a `401 Unauthorized` challenge, holding a nonce, and each request then carries a hash of the
+credentials, the nonce, the method and the URI.
+
+The challenge is answered transparently: the request is sent again once with the computed credentials, and
+the nonce is reused for the following requests, with an incremented nonce count, until the server sends a
+new challenge.
+
+The `MD5` and `SHA-256` algorithms and their session variants are supported, with the `auth` quality of
+protection or without any (RFC 2069). When the server offers several challenges, `SHA-256` is preferred.
+
+## Examples
+
+```rust,no_run
+use cdumay_http_client::{ClientBuilder, HttpClient};
+use cdumay_http_client::authentication::digest::DigestAuth;
+
+let client = HttpClient::new("https://appliance.example.com", None).unwrap()
+    .set_auth(DigestAuth::new("admin", "secret"));
+
+let status = client.get("/api/status".to_string(), None, None, None, None, None);
+```
+*/
+
+use crate::authentication::Authentication;
+use crate::utils::random_u64;
+use md5::Md5;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, WWW_AUTHENTICATE};
+use reqwest::{Method, Url};
+use sha2::{Digest, Sha256};
+use std::fmt::{Debug, Formatter};
+use std::sync::Mutex;
+
+/// The hash algorithm of a challenge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Algorithm {
+    Md5,
+    Sha256,
+}
+
+impl Algorithm {
+    /// Returns the lowercase hexadecimal hash of `data`.
+    fn hash(&self, data: &str) -> String {
+        match self {
+            Algorithm::Md5 => format!("{:x}", Md5::digest(data.as_bytes())),
+            Algorithm::Sha256 => format!("{:x}", Sha256::digest(data.as_bytes())),
+        }
+    }
+}
+
+/// A Digest challenge sent by the server, and the number of requests which answered it.
+#[derive(Debug, Clone, PartialEq)]
+struct Challenge {
+    realm: String,
+    nonce: String,
+    opaque: Option<String>,
+    algorithm: Algorithm,
+    session: bool,
+    qop: bool,
+    nc: u32,
+}
+
+impl Challenge {
+    /// Builds a challenge from its parameters, or returns `None` when it can't be answered.
+    fn new(params: &[(String, String)]) -> Option<Challenge> {
+        let param = |name: &str| params.iter().find(|(key, _)| key == name).map(|(_, value)| value.clone());
+        let (algorithm, session) = match param("algorithm").unwrap_or("MD5".into()).to_ascii_uppercase().as_str() {
+            "MD5" => (Algorithm::Md5, false),
+            "MD5-SESS" => (Algorithm::Md5, true),
+            "SHA-256" => (Algorithm::Sha256, false),
+            "SHA-256-SESS" => (Algorithm::Sha256, true),
+            _ => return None,
+        };
+        // Only the `auth` quality of protection is supported, `auth-int` requires hashing the body.
+        let qop = match param("qop") {
+            Some(qop) if qop.split(',').any(|qop| qop.trim().eq_ignore_ascii_case("auth")) => true,
+            Some(_) => return None,
+            None => false,
+        };
+        Some(Challenge {
+            realm: param("realm").unwrap_or_default(),
+            nonce: param("nonce")?,
+            opaque: param("opaque"),
+            algorithm,
+            session,
+            qop,
+            nc: 0,
+        })
+    }
+
+    /// Returns the name of the algorithm, as sent in the `Authorization` header.
+    fn algorithm_name(&self) -> &'static str {
+        match (self.algorithm, self.session) {
+            (Algorithm::Md5, false) => "MD5",
+            (Algorithm::Md5, true) => "MD5-sess",
+            (Algorithm::Sha256, false) => "SHA-256",
+            (Algorithm::Sha256, true) => "SHA-256-sess",
+        }
+    }
+}
+
+/// Parses the auth-params of a challenge, up to the next challenge, and returns them with the rest of
+/// the header value.
+fn parse_params(input: &str) -> (Vec<(String, String)>, &str) {
+    let mut params = Vec::new();
+    let mut rest = input;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+        let Some(equal) = rest.find('=') else { break };
+        let name = rest[..equal].trim();
+        // A space before the `=` starts the next challenge, such as `Basic realm=...`.
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            break;
+        }
+        let value = rest[equal + 1..].trim_start();
+        let value = match value.strip_prefix('"') {
+            Some(quoted) => {
+                let mut unquoted = String::new();
+                let mut chars = quoted.char_indices();
+                let mut end = None;
+                while let Some((index, c)) = chars.next() {
+                    match c {
+                        '\\' => unquoted.extend(chars.next().map(|(_, c)| c)),
+                        '"' => {
+                            end = Some(index);
+                            break;
+                        }
+                        c => unquoted.push(c),
+                    }
+                }
+                let Some(end) = end else { break };
+                rest = &quoted[end + 1..];
+                unquoted
+            }
+            None => {
+                let end = value.find(',').unwrap_or(value.len());
+                rest = &value[end..];
+                value[..end].trim().to_string()
+            }
+        };
+        params.push((name.to_ascii_lowercase(), value));
+    }
+    (params, rest)
+}
+
+/// Returns the Digest challenges of the `WWW-Authenticate` headers which can be answered.
+fn challenges(headers: &HeaderMap) -> Vec<Challenge> {
+    let mut challenges = Vec::new();
+    for value in headers.get_all(WWW_AUTHENTICATE).iter().filter_map(|value| value.to_str().ok()) {
+        let mut rest = value;
+        loop {
+            let lower = rest.to_ascii_lowercase();
+            let start = lower
+                .match_indices("digest ")
+                .map(|(index, _)| index)
+                .find(|index| *index == 0 || lower[..*index].trim_end().ends_with(','));
+            let Some(start) = start else { break };
+            let (params, next) = parse_params(&rest[start + "digest ".len()..]);
+            challenges.extend(Challenge::new(&params));
+            rest = next;
+        }
+    }
+    challenges
+}
+
+/// Escapes a value sent as a quoted string.
+fn quote(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Digest Authentication implementation.
+///
+/// The challenge is shared by all the requests of the client, including across threads and tasks. The
+/// password is never printed by the `Debug` implementation.
+///
+/// # Examples
+///
+/// ```rust
+/// use cdumay_http_client::authentication::digest::DigestAuth;
+///
+/// let auth = DigestAuth::new("admin", "secret");
+/// ```
+pub struct DigestAuth {
+    username: String,
+    password: String,
+    challenge: Mutex<Option<Challenge>>,
+}
+
+impl DigestAuth {
+    /// Creates a new Digest Authentication instance.
+    pub fn new<U: Into<String>, P: Into<String>>(username: U, password: P) -> DigestAuth {
+        DigestAuth {
+            username: username.into(),
+            password: password.into(),
+            challenge: Mutex::new(None),
+        }
+    }
+
+    /// Computes the `response` parameter answering `challenge`.
+    fn response(&self, challenge: &Challenge, method: &str, uri: &str, nc: &str, cnonce: &str) -> String {
+        let hash = |data: String| challenge.algorithm.hash(&data);
+        let mut ha1 = hash(format!("{}:{}:{}", self.username, challenge.realm, self.password));
+        if challenge.session {
+            ha1 = hash(format!("{}:{}:{}", ha1, challenge.nonce, cnonce));
+        }
+        let ha2 = hash(format!("{}:{}", method, uri));
+        match challenge.qop {
+            true => hash(format!("{}:{}:{}:{}:auth:{}", ha1, challenge.nonce, nc, cnonce, ha2)),
+            false => hash(format!("{}:{}:{}", ha1, challenge.nonce, ha2)),
+        }
+    }
+}
+
+impl Debug for DigestAuth {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DigestAuth")
+            .field("username", &self.username)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Authentication for DigestAuth {
+    fn username(&self) -> Option<String> { Some(self.username.clone()) }
+    fn password(&self) -> Option<String> { Some(self.password.clone()) }
+
+    /// Returns `None`: the Digest header depends on the request, see [`DigestAuth::authorize`].
+    fn as_header(&self) -> Option<(HeaderName, HeaderValue)> { None }
+
+    /// Generates the Digest Authentication header answering the last challenge of the server.
+    ///
+    /// Returns no header until a challenge was received.
+    fn authorize(&self, method: &Method, url: &Url) -> Vec<(HeaderName, HeaderValue)> {
+        let mut challenge = self.challenge.lock().unwrap_or_else(|err| err.into_inner());
+        let Some(challenge) = challenge.as_mut() else { return Vec::new() };
+        challenge.nc += 1;
+        let nc = format!("{:08x}", challenge.nc);
+        let cnonce = format!("{:016x}", random_u64());
+        let uri = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        };
+        let mut value = format!(
+            "Digest username=\"{}\", realm=\"{}\", nonce=\"{}\", uri=\"{}\", algorithm={}, response=\"{}\"",
+            quote(&self.username),
+            quote(&challenge.realm),
+            quote(&challenge.nonce),
+            quote(&uri),
+            challenge.algorithm_name(),
+            self.response(challenge, method.as_str(), &uri, &nc, &cnonce)
+        );
+        if let Some(opaque) = &challenge.opaque {
+            value.push_str(&format!(", opaque=\"{}\"", quote(opaque)));
+        }
+        if challenge.qop {
+            value.push_str(&format!(", qop=auth, nc={}, cnonce=\"{}\"", nc, cnonce));
+        }
+        match HeaderValue::from_str(&value) {
+            Ok(mut value) => {
+                value.set_sensitive(true);
+                vec![(AUTHORIZATION, value)]
+            }
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Stores the challenge of the server, preferring `SHA-256`, and asks for the request to be sent again.
+    fn on_unauthorized(&self, headers: &HeaderMap) -> bool {
+        let challenge = challenges(headers)
+            .into_iter()
+            .min_by_key(|challenge| challenge.algorithm != Algorithm::Sha256);
+        match challenge {
+            Some(challenge) => {
+                *self.challenge.lock().unwrap_or_else(|err| err.into_inner()) = Some(challenge);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use reqwest::header::{HeaderMap, HeaderValue, WWW_AUTHENTICATE};
+    use wiremock::{Match, Mock, MockServer, Request, ResponseTemplate};
+
+    use super::{challenges, parse_params, Algorithm, Challenge, DigestAuth};
+    use crate::{AsyncHttpClient, ClientBuilder, HttpClient};
+
+    // The example of RFC 7616, section 3.9.1.
+    const NONCE: &str = "7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v";
+    const CNONCE: &str = "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ";
+
+    fn challenge(algorithm: Algorithm) -> Challenge {
+        Challenge {
+            realm: "http-auth@example.org".into(),
+            nonce: NONCE.into(),
+            opaque: Some("FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS".into()),
+            algorithm,
+            session: false,
+            qop: true,
+            nc: 0,
+        }
+    }
+
+    #[test]
+    fn test_digest_response() {
+        let auth = DigestAuth::new("Mufasa", "Circle of Life");
+        assert_eq!(
+            auth.response(&challenge(Algorithm::Md5), "GET", "/dir/index.html", "00000001", CNONCE),
+            "8ca523f5e9506fed4657c9700eebdbec"
+        );
+        assert_eq!(
+            auth.response(&challenge(Algorithm::Sha256), "GET", "/dir/index.html", "00000001", CNONCE),
+            "753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1"
+        );
+    }
+
+    #[test]
+    fn test_digest_challenges() {
+        let mut headers = HeaderMap::new();
+        headers.append(
+            WWW_AUTHENTICATE,
+            HeaderValue::from_static(
+                r#"Basic realm="basic", Digest realm="http-auth@example.org", qop="auth, auth-int", algorithm=MD5, nonce="7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v", opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS""#,
+            ),
+        );
+        headers.append(
+            WWW_AUTHENTICATE,
+            HeaderValue::from_static(r#"Digest realm="unsupported", algorithm=SHA-512-256, nonce="n""#),
+        );
+        headers.append(
+            WWW_AUTHENTICATE,
+            HeaderValue::from_static(
+                r#"Digest realm="http-auth@example.org", qop="auth, auth-int", algorithm=SHA-256, nonce="7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v", opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS""#,
+            ),
+        );
+        assert_eq!(challenges(&headers), vec![challenge(Algorithm::Md5), challenge(Algorithm::Sha256)]);
+
+        let (params, rest) = parse_params(r#"realm="a \"quoted\" realm", stale=true, Basic realm="b""#);
+        assert_eq!(
+            params,
+            vec![("realm".into(), "a \"quoted\" realm".into()), ("stale".into(), "true".into())]
+        );
+        assert_eq!(rest, r#"Basic realm="b""#);
+    }
+
+    /// Matches the requests answering the challenge of the test server with the right password.
+    struct ValidDigest;
+
+    impl Match for ValidDigest {
+        fn matches(&self, request: &Request) -> bool {
+            let Some(value) = request.headers.get("authorization").and_then(|value| value.to_str().ok()) else {
+                return false;
+            };
+            let Some(value) = value.strip_prefix("Digest ") else { return false };
+            let (params, _) = parse_params(value);
+            let param = |name: &str| params.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str());
+            let challenge = Challenge {
+                realm: "appliance".into(),
+                nonce: "abc".into(),
+                opaque: None,
+                algorithm: Algorithm::Sha256,
+                session: false,
+                qop: true,
+                nc: 0,
+            };
+            let uri = param("uri").unwrap_or_default();
+            let expected = DigestAuth::new("admin", "secret").response(
+                &challenge,
+                request.method.as_str(),
+                uri,
+                param("nc").unwrap_or_default(),
+                param("cnonce").unwrap_or_default(),
+            );
+            uri == "/status?verbose=1" && param("response") == Some(expected.as_str())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_digest_auth() {
+        let server = MockServer::start().await;
+        Mock::given(ValidDigest)
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .mount(&server)
+            .await;
+        Mock::given(wiremock::matchers::any())
+            .respond_with(ResponseTemplate::new(401).insert_header(
+                "www-authenticate",
+                r#"Digest realm="appliance", qop="auth", algorithm=SHA-256, nonce="abc""#,
+            ))
+            .expect(3_u64)
+            .mount(&server)
+            .await;
+
+        let params = || Some([("verbose".to_string(), "1".to_string())].into());
+        let cli = AsyncHttpClient::new(&server.uri(), None).unwrap().set_auth(DigestAuth::new("admin", "secret"));
+        assert_eq!(cli.get("/status".into(), params(), None, None, None, None).await.unwrap(), "ok");
+        assert_eq!(cli.get("/status".into(), params(), None, None, None, None).await.unwrap(), "ok");
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 3);
+        let authorization = requests[2].headers.get("authorization").unwrap().to_str().unwrap();
+        assert!(authorization.contains("nc=00000002"));
+
+        let cli = AsyncHttpClient::new(&server.uri(), None)
+            .unwrap()
+            .set_retry_number(1)
+            .set_auth(DigestAuth::new("admin", "wrong"));
+        assert!(cli.get("/status".into(), params(), None, None, None, None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_digest_auth_blocking() {
+        let server = MockServer::start().await;
+        Mock::given(ValidDigest)
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .expect(1_u64)
+            .mount(&server)
+            .await;
+        Mock::given(wiremock::matchers::any())
+            .respond_with(ResponseTemplate::new(401).insert_header(
+                "www-authenticate",
+                r#"Digest realm="appliance", qop="auth", algorithm=SHA-256, nonce="abc""#,
+            ))
+            .expect(1_u64)
+            .mount(&server)
+            .await;
+
+        let cli = HttpClient::new(&server.uri(), None).unwrap().set_auth(DigestAuth::new("admin", "secret"));
+        let body = tokio::task::spawn_blocking(move || {
+            let params = Some([("verbose".to_string(), "1".to_string())].into());
+            cli.get("/status".into(), params, None, None, None, None).unwrap()
+        })
+        .await
+        .unwrap();
+        assert_eq!(body, "ok");
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].headers.get("authorization").is_none());
+        let authorization = requests[1].headers.get("authorization").unwrap().to_str().unwrap();
+        assert!(authorization.starts_with("Digest "));
+        assert!(authorization.contains("nc=00000001"));
+        assert!(authorization.contains(r#"nonce="abc""#));
+    }
+}
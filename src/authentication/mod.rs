@@ -8,7 +8,12 @@ different authentication methods and built-in implementations for common authent
 
 - Flexible authentication trait system
 - Built-in Basic Authentication support
+- Bearer tokens, static or returned by a provider for each request
+- API keys sent in a header or in the query string
+- OAuth2 client credentials, with cached and renewed access tokens
+- HTTP Digest Authentication, answering the server challenges
 - No Authentication option for public endpoints
+- Credentials refreshed after a `401 Unauthorized` response, and the request sent again
 - Easy to extend with custom authentication methods
 
 ## Usage Examples
@@ -49,6 +54,65 @@ let auth = BasicAuth::new(
 );
 ```
 
+### Bearer Authentication
+
+For endpoints that require a bearer token, such as an OAuth2 access token:
+
+```rust
+use cdumay_http_client::{ClientBuilder, HttpClient};
+use cdumay_http_client::authentication::bearer::BearerAuth;
+
+// With a static token
+let client = HttpClient::new("https://api.example.com", None).unwrap()
+    .set_auth(BearerAuth::new("your-token"));
+
+// With a token read for each request
+let auth = BearerAuth::with_provider(|| std::env::var("API_TOKEN").ok());
+```
+
+### API Key Authentication
+
+For APIs expecting a key in a header or in the query string:
+
+```rust
+use cdumay_http_client::{ClientBuilder, HttpClient};
+use cdumay_http_client::authentication::api_key::ApiKeyAuth;
+
+// Sends `X-API-Key: your-key`
+let client = HttpClient::new("https://api.example.com", None).unwrap()
+    .set_auth(ApiKeyAuth::header("X-API-Key", "your-key"));
+
+// Sends `?api_key=your-key`
+let auth = ApiKeyAuth::query("api_key", "your-key");
+```
+
+### OAuth2 Client Credentials
+
+For service to service calls authorized by an OAuth2 server; see [`oauth2`]:
+
+```rust
+use cdumay_http_client::{ClientBuilder, HttpClient};
+use cdumay_http_client::authentication::oauth2::OAuth2ClientCredentials;
+
+let auth = OAuth2ClientCredentials::new("https://auth.example.com/oauth/token", "my-service", "secret")
+    .set_scopes(vec!["users:read".to_string()]);
+
+let client = HttpClient::new("https://api.example.com", None).unwrap()
+    .set_auth(auth);
+```
+
+### Digest Authentication
+
+For servers and appliances that require HTTP Digest Authentication; see [`digest`]:
+
+```rust
+use cdumay_http_client::{ClientBuilder, HttpClient};
+use cdumay_http_client::authentication::digest::DigestAuth;
+
+let client = HttpClient::new("https://appliance.example.com", None).unwrap()
+    .set_auth(DigestAuth::new("admin", "secret"));
+```
+
 ### Custom Authentication
 
 Implement the `Authentication` trait for custom authentication methods:
@@ -59,15 +123,15 @@ use cdumay_http_client::authentication::Authentication;
 use reqwest::header::{HeaderName, HeaderValue, AUTHORIZATION};
 
 #[derive(Debug)]
-struct BearerAuth {
+struct TokenAuth {
     token: String,
 }
 
-impl Authentication for BearerAuth {
+impl Authentication for TokenAuth {
     fn username(&self) -> Option<String> { None }
     fn password(&self) -> Option<String> { None }
     fn as_header(&self) -> Option<(HeaderName, HeaderValue)> {
-        let value = format!("Bearer {}", self.token);
+        let value = format!("Token {}", self.token);
         Some((
             AUTHORIZATION,
             HeaderValue::from_str(&value).unwrap()
@@ -76,7 +140,7 @@ impl Authentication for BearerAuth {
 }
 
 // Using custom authentication
-let auth = BearerAuth {
+let auth = TokenAuth {
     token: "your-token".to_string()
 };
 
@@ -86,15 +150,28 @@ let client = HttpClient::new("https://api.example.com", None).unwrap()
 */
 
 use std::fmt::Debug;
-use reqwest::header::{HeaderName, HeaderValue};
+use std::future::Future;
+use std::pin::Pin;
+use cdumay_error::Result;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Method, Url};
 
+pub mod api_key;
 pub mod basic;
+pub mod bearer;
+pub mod digest;
+pub mod oauth2;
 
 /// Trait for implementing authentication methods.
 ///
 /// This trait should be implemented by any struct that provides authentication
 /// functionality. It requires implementing methods to get the username,
 /// password (if applicable), and to generate the appropriate authentication header.
+/// Implementations sending several headers or query parameters override
+/// [`as_headers`](Authentication::as_headers) and [`as_query`](Authentication::as_query).
+///
+/// Implementations must be `Send + Sync` so that they can be shared by the
+/// asynchronous clients across tasks.
 ///
 /// # Examples
 ///
@@ -118,7 +195,7 @@ pub mod basic;
 ///     }
 /// }
 /// ```
-pub trait Authentication: Debug {
+pub trait Authentication: Debug + Send + Sync {
     /// Returns the username if the authentication method uses one.
     fn username(&self) -> Option<String>;
     
@@ -131,6 +208,49 @@ pub trait Authentication: Debug {
     /// should be added to the request, or `Some((name, value))` with
     /// the appropriate header name and value for authentication.
     fn as_header(&self) -> Option<(HeaderName, HeaderValue)>;
+
+    /// Returns all the authentication headers, by default the one returned by
+    /// [`as_header`](Authentication::as_header).
+    fn as_headers(&self) -> Vec<(HeaderName, HeaderValue)> {
+        self.as_header().into_iter().collect()
+    }
+
+    /// Returns the authentication headers of a request to `url` (including the query parameters returned by
+    /// [`as_query`](Authentication::as_query)) using `method`.
+    ///
+    /// It is called for each attempt, and returns the headers of [`as_headers`](Authentication::as_headers)
+    /// by default. Schemes signing each request, such as Digest, override it.
+    fn authorize(&self, _method: &Method, _url: &Url) -> Vec<(HeaderName, HeaderValue)> {
+        self.as_headers()
+    }
+
+    /// Returns the query parameters added to the URL of each request, none by default.
+    fn as_query(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+
+    /// Called when a request is answered with `401 Unauthorized`, with the headers of the response.
+    ///
+    /// Implementations invalidate or refresh their credentials and return `true` to have the request sent
+    /// again once, with the new credentials, before the error is reported. Returns `false` by default.
+    fn on_unauthorized(&self, _headers: &HeaderMap) -> bool {
+        false
+    }
+
+    /// Called by the blocking clients before each attempt, before the headers are generated.
+    ///
+    /// Implementations obtaining their credentials from a remote service, such as
+    /// [`OAuth2ClientCredentials`](oauth2::OAuth2ClientCredentials), fetch or renew them here: an error
+    /// is returned to the caller instead of sending the request. Does nothing by default.
+    fn prepare(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called by the asynchronous clients before each attempt; this is the asynchronous version of
+    /// [`prepare`](Authentication::prepare), which must not block the executor.
+    fn prepare_async(&self) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>> {
+        Box::pin(async { Ok(()) })
+    }
 }
 
 /// A type that represents no authentication.
@@ -0,0 +1,311 @@
+/*!
+# Body Module
+
+This module provides [`Body`], the request body accepted by the blocking [`HttpClient`](crate::HttpClient).
+Besides in-memory text or bytes, a body can be streamed from a file or any `Read` source, so that large
+artifacts are uploaded without being loaded into memory, or built from a [multipart form](crate::multipart::Form). The length is sent as `Content-Length` when it
+is known, otherwise the body is sent with chunked transfer encoding.
+
+Since a failed request may be retried, the body must be sent again on each attempt:
+
+- in-memory bodies are simply cloned,
+- [`Body::file`] re-opens the file,
+- [`Body::seekable`] rewinds the reader to its position at the first attempt,
+- multipart forms are encoded again, re-opening their files,
+- [`Body::reader`] can only be read once: a retry fails with a
+  [`RequestError`](crate::errors::client::RequestError) explaining that the body is not replayable.
+
+## Examples
+
+```rust,no_run
+use cdumay_http_client::{Body, ClientBuilder, HttpClient};
+
+let client = HttpClient::new("https://artifacts.example.com", None).unwrap()
+    .set_retry_number(3);
+
+let result = client.put_body(
+    "/releases/app-1.0.tar.gz".to_string(),
+    None,
+    Body::file("target/app-1.0.tar.gz"),
+    None,
+    None,
+    None,
+    None,
+);
+```
+*/
+
+use bytes::Bytes;
+use cdumay_context::Context;
+use cdumay_error::Result;
+use reqwest::header::HeaderValue;
+use serde_value::Value;
+use std::fmt;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::errors::client::RequestError;
+use crate::errors::io_error_serialize;
+use crate::multipart::Form;
+
+/// A request body, held in memory or streamed from a file or a reader.
+pub struct Body {
+    kind: Kind,
+}
+
+enum Kind {
+    Bytes(Bytes),
+    File(PathBuf),
+    Reader(Mutex<Option<Box<dyn Read + Send>>>, Option<u64>),
+    Seekable(Arc<Mutex<Seekable>>, Option<u64>),
+    Multipart(Form),
+}
+
+/// A reader shared between the body and the requests streaming it.
+struct Seekable {
+    reader: Box<dyn ReadSeek>,
+    start: Option<u64>,
+}
+
+trait ReadSeek: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> ReadSeek for T {}
+
+/// Reads the shared reader of a [`Body::seekable`] body.
+struct SharedReader(Arc<Mutex<Seekable>>);
+
+impl Read for SharedReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        lock(&self.0).reader.read(buf)
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+impl Body {
+    /// Creates a body streamed from the file at `path`, which is opened on each attempt.
+    ///
+    /// The length of the file is sent as `Content-Length`.
+    pub fn file<P: Into<PathBuf>>(path: P) -> Body {
+        Body { kind: Kind::File(path.into()) }
+    }
+
+    /// Creates a body streamed from `reader`, which can only be sent once.
+    ///
+    /// If `length` is `None`, the body is sent with chunked transfer encoding.
+    pub fn reader<R: Read + Send + 'static>(reader: R, length: Option<u64>) -> Body {
+        Body {
+            kind: Kind::Reader(Mutex::new(Some(Box::new(reader))), length),
+        }
+    }
+
+    /// Creates a body streamed from `reader`, which is rewound to its initial position on each retry.
+    ///
+    /// If `length` is `None`, the body is sent with chunked transfer encoding.
+    pub fn seekable<R: Read + Seek + Send + 'static>(reader: R, length: Option<u64>) -> Body {
+        let seekable = Seekable {
+            reader: Box::new(reader),
+            start: None,
+        };
+        Body {
+            kind: Kind::Seekable(Arc::new(Mutex::new(seekable)), length),
+        }
+    }
+
+    /// Returns whether the body can be sent again after a failed attempt.
+    pub fn is_replayable(&self) -> bool {
+        !matches!(self.kind, Kind::Reader(..))
+    }
+
+    /// Returns the body content if it is held in memory.
+    pub fn as_bytes(&self) -> Option<&Bytes> {
+        match &self.kind {
+            Kind::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// Returns the `Content-Type` header required by the body, overriding the client default.
+    pub(crate) fn content_type(&self) -> Option<HeaderValue> {
+        match &self.kind {
+            Kind::Multipart(form) => Some(form.content_type()),
+            _ => None,
+        }
+    }
+
+    /// Returns the in-memory content, or an error for streamed bodies, which need a blocking client.
+    pub(crate) fn into_bytes(self, context: Context) -> Result<Bytes> {
+        match self.kind {
+            Kind::Bytes(bytes) => Ok(bytes),
+            _ => Err(RequestError::new()
+                .set_message("Streamed bodies can only be sent by blocking clients".into())
+                .set_details(context.into())
+                .into()),
+        }
+    }
+
+    /// Prepares the body to be sent by an attempt, re-opening or rewinding its source.
+    pub(crate) fn transport(&self, context: Context) -> Result<reqwest::blocking::Body> {
+        match &self.kind {
+            Kind::Bytes(bytes) => Ok(bytes.clone().into()),
+            Kind::File(path) => {
+                let file = File::open(path).map_err(|err| {
+                    let mut context = context.clone();
+                    context.insert("file".into(), Value::String(path.display().to_string()));
+                    io_error_serialize(&err, Some(context))
+                })?;
+                Ok(file.into())
+            }
+            Kind::Reader(reader, length) => match lock(reader).take() {
+                Some(reader) => Ok(sized(reader, *length)),
+                None => Err(RequestError::new()
+                    .set_message("The request body is not replayable: its reader was consumed by a previous attempt".into())
+                    .set_details(context.into())
+                    .into()),
+            },
+            Kind::Seekable(shared, length) => {
+                let mut seekable = lock(shared);
+                let rewind = match seekable.start {
+                    Some(start) => seekable.reader.seek(SeekFrom::Start(start)),
+                    None => seekable.reader.stream_position(),
+                };
+                let start = rewind.map_err(|err| io_error_serialize(&err, Some(context)))?;
+                seekable.start = Some(start);
+                Ok(sized(SharedReader(shared.clone()), *length))
+            }
+            Kind::Multipart(form) => {
+                let (reader, length) = form.open(context)?;
+                Ok(sized(reader, Some(length)))
+            }
+        }
+    }
+}
+
+fn sized<R: Read + Send + 'static>(reader: R, length: Option<u64>) -> reqwest::blocking::Body {
+    match length {
+        Some(length) => reqwest::blocking::Body::sized(reader, length),
+        None => reqwest::blocking::Body::new(reader),
+    }
+}
+
+impl fmt::Debug for Body {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            Kind::Bytes(bytes) => f.debug_tuple("Body").field(bytes).finish(),
+            Kind::File(path) => f.debug_tuple("Body::file").field(path).finish(),
+            Kind::Reader(_, length) => f.debug_tuple("Body::reader").field(length).finish(),
+            Kind::Seekable(_, length) => f.debug_tuple("Body::seekable").field(length).finish(),
+            Kind::Multipart(form) => f.debug_tuple("Body::multipart").field(form).finish(),
+        }
+    }
+}
+
+impl From<Bytes> for Body {
+    fn from(bytes: Bytes) -> Body {
+        Body { kind: Kind::Bytes(bytes) }
+    }
+}
+
+impl From<Form> for Body {
+    fn from(form: Form) -> Body {
+        Body { kind: Kind::Multipart(form) }
+    }
+}
+
+impl From<Vec<u8>> for Body {
+    fn from(bytes: Vec<u8>) -> Body {
+        Body::from(Bytes::from(bytes))
+    }
+}
+
+impl From<String> for Body {
+    fn from(text: String) -> Body {
+        Body::from(Bytes::from(text))
+    }
+}
+
+impl From<&'static str> for Body {
+    fn from(text: &'static str) -> Body {
+        Body::from(Bytes::from_static(text.as_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use wiremock::matchers::{body_bytes, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use crate::errors::client::REQUEST_ERROR;
+    use crate::{Body, ClientBuilder, HttpClient};
+
+    async fn flaky_server(body: &[u8]) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(body_bytes(body.to_vec()))
+            .respond_with(ResponseTemplate::new(200).set_body_string("stored"))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    fn put(uri: String, body: Body) -> cdumay_error::Result<String> {
+        let cli = HttpClient::new(&uri, None).unwrap().set_retry_number(3).set_retry_delay(0);
+        cli.put_body("/artifact".into(), None, body, None, None, None, None)
+    }
+
+    #[tokio::test]
+    async fn test_body_file() {
+        let content = b"artifact content".to_vec();
+        let file = std::env::temp_dir().join(format!("cdumay_http_client_{}_upload.bin", std::process::id()));
+        std::fs::write(&file, &content).unwrap();
+        let server = flaky_server(&content).await;
+        let (uri, upload) = (server.uri(), file.clone());
+        let result = tokio::task::spawn_blocking(move || put(uri, Body::file(upload)))
+            .await
+            .unwrap();
+        assert_eq!(result.unwrap(), "stored");
+        std::fs::remove_file(&file).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_body_seekable() {
+        let server = flaky_server(b"0123456789").await;
+        let uri = server.uri();
+        let mut reader = Cursor::new(b"xx0123456789".to_vec());
+        reader.set_position(2);
+        let result = tokio::task::spawn_blocking(move || put(uri, Body::seekable(reader, Some(10))))
+            .await
+            .unwrap();
+        assert_eq!(result.unwrap(), "stored");
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests.iter().all(|request| request.body == b"0123456789"));
+    }
+
+    #[tokio::test]
+    async fn test_body_reader_not_replayable() {
+        let server = flaky_server(b"streamed").await;
+        let uri = server.uri();
+        let body = Body::reader(Cursor::new(b"streamed".to_vec()), None);
+        assert!(!body.is_replayable());
+        let err = tokio::task::spawn_blocking(move || put(uri, body))
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(err.kind, REQUEST_ERROR);
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].body, b"streamed");
+    }
+}
@@ -0,0 +1,1118 @@
+/*!
+# Async HTTP Client Module
+
+This module provides the non-blocking counterpart of the [`HttpClient`](crate::HttpClient). It is built on top
+of the asynchronous `reqwest` client and is meant to be used from within a tokio runtime, without wrapping
+every call in `spawn_blocking`.
+
+## Features
+
+- Same configuration surface as the blocking client through the [`ClientBuilder`](crate::ClientBuilder) trait
+- Support for all standard HTTP methods (GET, POST, PUT, DELETE, HEAD)
+- Automatic retry with configurable attempts and delay, using non-blocking sleeps
+- Connection pooling across requests
+- Same `no_retry_on` semantics and error mapping as the blocking client
+
+## Examples
+
+### Basic Usage
+
+```rust
+use cdumay_http_client::{AsyncHttpClient, ClientBuilder};
+
+async fn fetch_users() {
+    let client = AsyncHttpClient::new("https://api.example.com", None).unwrap()
+        .set_timeout(30)       // 30 seconds timeout
+        .set_retry_number(3)   // Retry 3 times
+        .set_retry_delay(5);   // 5 seconds between retries
+
+    // Make a GET request
+    let result = client.get(
+        "/users".to_string(),
+        None,                  // No query parameters
+        None,                  // No additional headers
+        None,                  // Use default timeout
+        None,                  // Use default retry behavior
+        None,                  // No context
+    ).await;
+}
+```
+
+### Error Handling
+
+```rust
+use cdumay_http_client::{AsyncHttpClient, ClientBuilder};
+use cdumay_http_client::errors::http::{NOT_FOUND, FORBIDDEN};
+
+async fn fetch_user() {
+    let client = AsyncHttpClient::new("https://api.example.com", None).unwrap();
+
+    let result = client.get(
+        "/users/123".to_string(),
+        None,
+        None,
+        None,
+        Some(vec![NOT_FOUND, FORBIDDEN]),
+        None,
+    ).await;
+}
+```
+*/
+
+use bytes::Bytes;
+use cdumay_context::Context;
+use cdumay_error::{Error, ErrorKind, Result};
+use chrono::Utc;
+use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode, Url};
+use serde_value::Value;
+use std::collections::HashMap;
+use std::future::Future;
+use std::ops::Deref;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::authentication::Authentication;
+use crate::errors::client::{InvalidHeaderValue, InvalidUrl};
+use crate::errors::{http_error_serialize, http_resp_error};
+use crate::retry::{ConstantBackoff, RetryPolicy};
+use crate::request::{HttpRequest, RequestOptions};
+use crate::response::HttpResponse;
+use crate::tls::TlsConfig;
+use crate::utils::{
+    authentication, build_url, get_or_try_init, request_headers, retry_number, strip_auth_query, Attempts, Outcome,
+};
+use crate::ClientBuilder;
+
+/// Base trait for asynchronous HTTP client implementations.
+///
+/// This trait is the non-blocking counterpart of [`BaseClient`](crate::BaseClient): it exposes the same
+/// configuration getters, and its request methods return futures which must be awaited.
+pub trait AsyncBaseClient {
+    /// Returns the root URL for all requests.
+    fn url_root(&self) -> &Url;
+
+    /// Returns the configured timeout in seconds.
+    fn timeout(&self) -> &u64;
+
+    /// Returns the configured headers.
+    fn headers(&self) -> &HeaderMap;
+
+    /// Returns the configured authentication method, if any.
+    fn auth(&self) -> Option<&Box<dyn Authentication>>;
+
+    /// Returns whether SSL verification is enabled.
+    fn ssl_verify(&self) -> bool;
+
+    /// Returns the TLS configuration.
+    fn tls_config(&self) -> &TlsConfig;
+
+    /// Returns the number of retry attempts for failed requests.
+    fn retry_number(&self) -> u64;
+
+    /// Returns the policy deciding whether and when a failed request is retried.
+    fn retry_policy(&self) -> &dyn RetryPolicy;
+
+    /// Returns the maximum delay, in seconds, honoured from a `Retry-After` header.
+    fn max_retry_after(&self) -> u64;
+
+    /// Returns whether requests using non-idempotent methods (`POST`, `PATCH`...) are retried.
+    fn retry_non_idempotent(&self) -> bool;
+
+    /// Returns whether an `Idempotency-Key` header is generated for `POST` and `PATCH` requests.
+    fn auto_idempotency_key(&self) -> bool;
+
+    /// Returns how long, in seconds, idle pooled connections are kept alive.
+    fn pool_idle_timeout(&self) -> Option<u64>;
+
+    /// Returns the maximum number of idle pooled connections per host.
+    fn pool_max_idle_per_host(&self) -> usize;
+
+    /// Returns the pooled transport shared by all the requests of this client.
+    ///
+    /// The transport is built from the client settings on first use and then reused, so that
+    /// connections, TLS sessions and DNS results are kept across requests.
+    fn transport(&self) -> Result<Client>;
+
+    /// Builds a new transport from the TLS and connection pool settings.
+    fn build_transport(&self) -> Result<Client> {
+        let builder = Client::builder()
+            .pool_idle_timeout(self.pool_idle_timeout().map(Duration::from_secs))
+            .pool_max_idle_per_host(self.pool_max_idle_per_host());
+        self.tls_config()
+            .apply_async(builder, self.ssl_verify())
+            .build()
+            .map_err(|err| http_error_serialize(&err, None))
+    }
+
+    /// Internal method to wrap request execution with error handling.
+    ///
+    /// Transport failures (connection refused, DNS failure, timeout...) are returned as
+    /// [`NetworkError`](crate::errors::client::NetworkError) and retried by [`AsyncBaseClient::execute_raw`].
+    fn _request_wrapper(
+        &self,
+        req: RequestBuilder,
+        context: Option<Context>,
+    ) -> impl Future<Output = Result<Response>> + Send {
+        async move { req.send().await.map_err(|err| http_error_serialize(&err.without_url(), context)) }
+    }
+
+    /// Builds the error of an unsuccessful response from its status, headers and body.
+    ///
+    /// The default implementation parses `application/problem+json` bodies into the error details (see
+    /// [`problem`](crate::errors::problem)); clients override it to map their own error bodies.
+    fn response_error(&self, status: StatusCode, headers: &HeaderMap, body: &[u8], context: Option<Context>) -> Error {
+        http_resp_error(status, headers, body, context)
+    }
+
+    /// Makes an HTTP request with the specified parameters.
+    ///
+    /// This is the asynchronous version of [`BaseClient::execute_raw`](crate::BaseClient::execute_raw); it
+    /// accepts the same arguments, applies the same retry logic and reports the same errors. Retry delays
+    /// are awaited using `tokio::time::sleep`, so they never block the executor.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - HTTP method to use
+    /// * `path` - Request path relative to the root URL
+    /// * `params` - Optional query parameters
+    /// * `data` - Optional request body
+    /// * `headers` - Optional additional headers
+    /// * `timeout` - Optional custom timeout for this request
+    /// * `no_retry_on` - Optional list of error kinds that should not trigger retry
+    /// * `context` - Optional context for error reporting
+    /// * `options` - Optional settings overriding the client configuration for this request
+    ///
+    /// # Returns
+    ///
+    /// Returns `Result<(Response, u64)>` which is:
+    /// - `Ok((Response, u64))` containing the response, whose body is left unread so that it can be
+    ///   streamed, and the number of attempts, if successful
+    /// - `Err` with detailed error information if the request fails
+    ///
+    /// A `304 Not Modified` response, answering a conditional request, is returned like a successful one.
+    fn execute_raw(
+        &self,
+        method: Method,
+        path: String,
+        params: Option<HashMap<String, String>>,
+        data: Option<Bytes>,
+        headers: Option<HeaderMap>,
+        timeout: Option<u64>,
+        no_retry_on: Option<Vec<ErrorKind>>,
+        context: Option<Context>,
+        options: Option<RequestOptions>,
+    ) -> impl Future<Output = Result<(Response, u64)>> + Send
+    where
+        Self: Sync,
+    {
+        async move {
+            let url = build_url(self.url_root(), path, params)?;
+            let options = options.unwrap_or_default();
+            let headers = request_headers(
+                self.headers(),
+                headers,
+                &method,
+                options.idempotency_key.unwrap_or(self.auto_idempotency_key()),
+            );
+            let mut attempts = Attempts::new(
+                &method,
+                &url,
+                context,
+                no_retry_on,
+                retry_number(
+                    &method,
+                    &headers,
+                    options.retry_non_idempotent.unwrap_or(self.retry_non_idempotent()),
+                    options.retry_number.unwrap_or(self.retry_number()),
+                ),
+                self.retry_policy(),
+                self.max_retry_after(),
+            );
+            let mut req = self
+                .transport()?
+                .request(method.clone(), url.clone())
+                .timeout(Duration::from_secs(timeout.unwrap_or(*self.timeout())))
+                .headers(headers);
+            if let Some(txt) = data {
+                req = req.body(txt);
+            }
+            loop {
+                let err_context = attempts.next();
+                let req = req.try_clone().ok_or_else(|| attempts.clone_error())?;
+                if let Some(auth) = self.auth() {
+                    auth.prepare_async().await?;
+                }
+                let (auth_headers, auth_query) = authentication(self.auth().map(|auth| auth.as_ref()), &method, &url);
+                let req = req.headers(auth_headers).query(&auth_query);
+                let err = match self._request_wrapper(req, Some(err_context.clone())).await {
+                    Ok(resp) => {
+                        let auth = self.auth().map(|auth| auth.as_ref());
+                        match attempts.response(resp.status(), resp.headers(), resp.content_length(), auth) {
+                            Outcome::Success => return Ok((resp, attempts.count())),
+                            Outcome::Reauthenticate => continue,
+                            Outcome::Failure => {
+                                let (status, headers) = (resp.status(), resp.headers().clone());
+                                let body = resp.bytes().await.unwrap_or_default();
+                                self.response_error(status, &headers, &body, Some(err_context))
+                            }
+                        }
+                    }
+                    Err(err) => attempts.transport_error(err)?,
+                };
+                tokio::time::sleep(attempts.failed(err)?).await;
+            }
+        }
+    }
+
+    /// Makes an HTTP request with the specified parameters and reads the whole response.
+    ///
+    /// This method accepts the same arguments as [`execute_raw`](AsyncBaseClient::execute_raw).
+    ///
+    /// # Returns
+    ///
+    /// Returns `Result<HttpResponse>` which is:
+    /// - `Ok(HttpResponse)` containing the status, headers and body if successful
+    /// - `Err` with detailed error information if the request fails
+    fn execute(
+        &self,
+        method: Method,
+        path: String,
+        params: Option<HashMap<String, String>>,
+        data: Option<Bytes>,
+        headers: Option<HeaderMap>,
+        timeout: Option<u64>,
+        no_retry_on: Option<Vec<ErrorKind>>,
+        context: Option<Context>,
+        options: Option<RequestOptions>,
+    ) -> impl Future<Output = Result<HttpResponse>> + Send
+    where
+        Self: Sync,
+    {
+        async move {
+            let start = Utc::now();
+            let mut context = context.unwrap_or_default();
+            let (resp, attempts) = self
+                .execute_raw(
+                    method.clone(),
+                    path,
+                    params,
+                    data,
+                    headers,
+                    timeout,
+                    no_retry_on,
+                    Some(context.clone()),
+                    options,
+                )
+                .await?;
+            let status = resp.status();
+            let headers = resp.headers().clone();
+            let url = strip_auth_query(resp.url(), self.auth().map(|auth| auth.as_ref()));
+            context.insert("url".into(), Value::String(url.to_string()));
+            context.insert("method".into(), Value::String(method.to_string()));
+            let body = resp
+                .bytes()
+                .await
+                .map_err(|err| http_error_serialize(&err, Some(context)))?;
+            let elapsed = { Utc::now() - start }.to_std().unwrap_or_default();
+            Ok(HttpResponse::new(method, status, headers, url, attempts, elapsed, body))
+        }
+    }
+
+    /// Makes an HTTP request with the specified parameters and returns the response body.
+    ///
+    /// This is a shortcut for [`execute`](AsyncBaseClient::execute) which drops the status and the
+    /// headers of the response; it accepts the same arguments.
+    fn do_request(
+        &self,
+        method: Method,
+        path: String,
+        params: Option<HashMap<String, String>>,
+        data: Option<String>,
+        headers: Option<HeaderMap>,
+        timeout: Option<u64>,
+        no_retry_on: Option<Vec<ErrorKind>>,
+        context: Option<Context>,
+    ) -> impl Future<Output = Result<String>> + Send
+    where
+        Self: Sync,
+    {
+        async move {
+            Ok(self
+                .execute(method, path, params, data.map(Bytes::from), headers, timeout, no_retry_on, context, None)
+                .await?
+                .into_text())
+        }
+    }
+
+    /// Makes an HEAD request with the specified parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Request path relative to the root URL
+    /// * `params` - Optional query parameters
+    /// * `headers` - Optional additional headers
+    /// * `timeout` - Optional custom timeout for this request
+    /// * `no_retry_on` - Optional list of error kinds that should not trigger retry
+    /// * `context` - Optional context for error reporting
+    ///
+    /// # Returns
+    ///
+    /// Returns `Result<()>` which is:
+    /// - `Ok(())` No response is required
+    /// - `Err` with detailed error information if the request fails
+    fn head(
+        &self,
+        path: String,
+        params: Option<HashMap<String, String>>,
+        headers: Option<HeaderMap>,
+        timeout: Option<u64>,
+        no_retry_on: Option<Vec<ErrorKind>>,
+        context: Option<Context>,
+    ) -> impl Future<Output = Result<()>> + Send
+    where
+        Self: Sync,
+    {
+        async move {
+            self.do_request(Method::HEAD, path, params, None, headers, timeout, no_retry_on, context)
+                .await?;
+            Ok(())
+        }
+    }
+
+    /// Makes an OPTIONS request, to discover the methods allowed on a resource.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Request path relative to the root URL
+    /// * `params` - Optional query parameters
+    /// * `headers` - Optional additional headers, such as the `Origin` and
+    ///   `Access-Control-Request-Method` headers of a CORS preflight request
+    /// * `timeout` - Optional custom timeout for this request
+    /// * `no_retry_on` - Optional list of error kinds that should not trigger retry
+    /// * `context` - Optional context for error reporting
+    ///
+    /// # Returns
+    ///
+    /// Returns `Result<Vec<Method>>` which is:
+    /// - `Ok(Vec<Method>)` containing the methods listed by the `Allow` header of the response
+    /// - `Err` with detailed error information if the request fails
+    fn options(
+        &self,
+        path: String,
+        params: Option<HashMap<String, String>>,
+        headers: Option<HeaderMap>,
+        timeout: Option<u64>,
+        no_retry_on: Option<Vec<ErrorKind>>,
+        context: Option<Context>,
+    ) -> impl Future<Output = Result<Vec<Method>>> + Send
+    where
+        Self: Sync,
+    {
+        async move {
+            Ok(self
+                .execute(Method::OPTIONS, path, params, None, headers, timeout, no_retry_on, context, None)
+                .await?
+                .allowed_methods())
+        }
+    }
+}
+
+/// Asynchronous HTTP client implementation with retry capabilities and configurable settings.
+///
+/// This struct is the non-blocking counterpart of [`HttpClient`](crate::HttpClient). It is configured
+/// through the same [`ClientBuilder`] trait and offers the same request methods, which return futures.
+///
+/// # Examples
+///
+/// ```rust
+/// use cdumay_http_client::{AsyncHttpClient, ClientBuilder};
+/// use cdumay_http_client::authentication::basic::BasicAuth;
+///
+/// async fn fetch_users() {
+///     let client = AsyncHttpClient::new("https://api.example.com", None).unwrap()
+///         .set_timeout(30)
+///         .set_auth(BasicAuth::new(
+///             "username".to_string(),
+///             Some("password".to_string())
+///         ))
+///         .set_retry_number(3)
+///         .set_retry_delay(5);
+///
+///     let result = client.get("/users".to_string(), None, None, None, None, None).await;
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsyncHttpClient {
+    url_root: Url,
+    timeout: u64,
+    headers: HeaderMap,
+    auth: Option<Box<dyn Authentication>>,
+    ssl_verify: bool,
+    tls_config: TlsConfig,
+    retry_number: u64,
+    retry_policy: Box<dyn RetryPolicy>,
+    max_retry_after: u64,
+    retry_non_idempotent: bool,
+    auto_idempotency_key: bool,
+    pool_idle_timeout: Option<u64>,
+    pool_max_idle_per_host: usize,
+    transport: OnceLock<reqwest::Client>,
+}
+
+impl ClientBuilder for AsyncHttpClient {
+    fn new(url_root: &str, context: Option<&mut Context>) -> Result<Self> {
+        Ok(AsyncHttpClient {
+            url_root: Url::parse(url_root.trim_end_matches("/")).map_err(|err| {
+                InvalidUrl::new()
+                    .set_message(format!("Failed to parse URL: {:?}", err))
+                    .set_details({
+                        let mut err_context = Context::new();
+                        if let Some(ctx) = context {
+                            err_context.extend(ctx.deref().clone().into());
+                        };
+                        err_context.insert("url".to_string(), Value::String(url_root.to_string()));
+                        err_context.into()
+                    })
+            })?,
+            timeout: 10,
+            headers: {
+                let mut headers = HeaderMap::new();
+                headers.append(
+                    USER_AGENT,
+                    HeaderValue::from_str(&format!(
+                        "{}/{}",
+                        env!("CARGO_PKG_NAME"),
+                        env!("CARGO_PKG_VERSION")
+                    ))
+                    .map_err(|err| InvalidHeaderValue::new().set_message(err.to_string()))?,
+                );
+                headers
+            },
+            auth: None,
+            ssl_verify: true,
+            tls_config: TlsConfig::default(),
+            retry_number: 10,
+            retry_policy: Box::new(ConstantBackoff::new(Duration::from_secs(30))),
+            max_retry_after: 120,
+            retry_non_idempotent: false,
+            auto_idempotency_key: false,
+            pool_idle_timeout: Some(90),
+            pool_max_idle_per_host: usize::MAX,
+            transport: OnceLock::new(),
+        })
+    }
+
+    fn set_timeout(mut self, timeout: u64) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn set_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers.extend(headers);
+        self
+    }
+
+    fn set_auth<A: Authentication + 'static>(mut self, auth: A) -> Self {
+        self.auth = Some(Box::new(auth));
+        self
+    }
+
+    fn set_ssl_verify(mut self, ssl_verify: bool) -> Self {
+        self.ssl_verify = ssl_verify;
+        self.transport = OnceLock::new();
+        self
+    }
+
+    fn set_tls_config(mut self, tls_config: TlsConfig) -> Self {
+        self.tls_config = tls_config;
+        self.transport = OnceLock::new();
+        self
+    }
+
+    fn set_retry_number(mut self, retry_number: u64) -> Self {
+        if retry_number == 0 {
+            panic!("Try number MUST be > 0 !");
+        }
+        self.retry_number = retry_number;
+        self
+    }
+
+    fn set_retry_delay(mut self, retry_delay: u64) -> Self {
+        self.retry_policy = Box::new(ConstantBackoff::new(Duration::from_secs(retry_delay)));
+        self
+    }
+
+    fn set_retry_policy<P: RetryPolicy + 'static>(mut self, policy: P) -> Self {
+        self.retry_policy = Box::new(policy);
+        self
+    }
+
+    fn set_max_retry_after(mut self, max_retry_after: u64) -> Self {
+        self.max_retry_after = max_retry_after;
+        self
+    }
+
+    fn set_retry_non_idempotent(mut self, retry_non_idempotent: bool) -> Self {
+        self.retry_non_idempotent = retry_non_idempotent;
+        self
+    }
+
+    fn set_auto_idempotency_key(mut self, auto_idempotency_key: bool) -> Self {
+        self.auto_idempotency_key = auto_idempotency_key;
+        self
+    }
+
+    fn set_pool_idle_timeout(mut self, idle_timeout: Option<u64>) -> Self {
+        self.pool_idle_timeout = idle_timeout;
+        self.transport = OnceLock::new();
+        self
+    }
+
+    fn set_pool_max_idle_per_host(mut self, max_idle: usize) -> Self {
+        self.pool_max_idle_per_host = max_idle;
+        self.transport = OnceLock::new();
+        self
+    }
+}
+
+impl AsyncBaseClient for AsyncHttpClient {
+    fn url_root(&self) -> &Url {
+        &self.url_root
+    }
+
+    fn timeout(&self) -> &u64 {
+        &self.timeout
+    }
+
+    fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    fn auth(&self) -> Option<&Box<dyn Authentication>> {
+        self.auth.as_ref()
+    }
+
+    fn ssl_verify(&self) -> bool {
+        self.ssl_verify
+    }
+
+    fn tls_config(&self) -> &TlsConfig {
+        &self.tls_config
+    }
+
+    fn retry_number(&self) -> u64 {
+        self.retry_number
+    }
+
+    fn retry_policy(&self) -> &dyn RetryPolicy {
+        self.retry_policy.as_ref()
+    }
+
+    fn max_retry_after(&self) -> u64 {
+        self.max_retry_after
+    }
+
+    fn retry_non_idempotent(&self) -> bool {
+        self.retry_non_idempotent
+    }
+
+    fn auto_idempotency_key(&self) -> bool {
+        self.auto_idempotency_key
+    }
+
+    fn pool_idle_timeout(&self) -> Option<u64> {
+        self.pool_idle_timeout
+    }
+
+    fn pool_max_idle_per_host(&self) -> usize {
+        self.pool_max_idle_per_host
+    }
+
+    fn transport(&self) -> Result<reqwest::Client> {
+        get_or_try_init(&self.transport, || self.build_transport())
+    }
+}
+
+impl AsyncHttpClient {
+    /// Starts building a request to `path`, relative to the root URL.
+    ///
+    /// This is a fluent alternative to the methods below; see [`HttpRequest`].
+    pub fn request<P: Into<String>>(&self, method: Method, path: P) -> HttpRequest<'_, AsyncHttpClient> {
+        HttpRequest::new(self, method, path)
+    }
+
+    /// Starts building a request using a method given by name, such as the WebDAV `PROPFIND` or `MKCOL`.
+    ///
+    /// An invalid method name is reported as a [`RequestError`](crate::errors::client::RequestError) when
+    /// the request is sent.
+    pub fn custom_request<P: Into<String>>(&self, method: &str, path: P) -> HttpRequest<'_, AsyncHttpClient> {
+        HttpRequest::custom(self, method, path)
+    }
+
+    /// Makes a GET request.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Request path relative to the root URL
+    /// * `params` - Optional query parameters
+    /// * `headers` - Optional additional headers
+    /// * `timeout` - Optional custom timeout for this request
+    /// * `no_retry_on` - Optional list of error kinds that should not trigger retry
+    /// * `context` - Optional context for error reporting
+    pub async fn get(
+        &self,
+        path: String,
+        params: Option<HashMap<String, String>>,
+        headers: Option<HeaderMap>,
+        timeout: Option<u64>,
+        no_retry_on: Option<Vec<ErrorKind>>,
+        context: Option<Context>,
+    ) -> Result<String> {
+        self.do_request(Method::GET, path, params, None, headers, timeout, no_retry_on, context)
+            .await
+    }
+
+    /// Makes a POST request.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Request path relative to the root URL
+    /// * `params` - Optional query parameters
+    /// * `data` - Optional request body
+    /// * `headers` - Optional additional headers
+    /// * `timeout` - Optional custom timeout for this request
+    /// * `no_retry_on` - Optional list of error kinds that should not trigger retry
+    /// * `context` - Optional context for error reporting
+    pub async fn post(
+        &self,
+        path: String,
+        params: Option<HashMap<String, String>>,
+        data: Option<String>,
+        headers: Option<HeaderMap>,
+        timeout: Option<u64>,
+        no_retry_on: Option<Vec<ErrorKind>>,
+        context: Option<Context>,
+    ) -> Result<String> {
+        self.do_request(Method::POST, path, params, data, headers, timeout, no_retry_on, context)
+            .await
+    }
+
+    /// Makes a PUT request.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Request path relative to the root URL
+    /// * `params` - Optional query parameters
+    /// * `data` - Optional request body
+    /// * `headers` - Optional additional headers
+    /// * `timeout` - Optional custom timeout for this request
+    /// * `no_retry_on` - Optional list of error kinds that should not trigger retry
+    /// * `context` - Optional context for error reporting
+    pub async fn put(
+        &self,
+        path: String,
+        params: Option<HashMap<String, String>>,
+        data: Option<String>,
+        headers: Option<HeaderMap>,
+        timeout: Option<u64>,
+        no_retry_on: Option<Vec<ErrorKind>>,
+        context: Option<Context>,
+    ) -> Result<String> {
+        self.do_request(Method::PUT, path, params, data, headers, timeout, no_retry_on, context)
+            .await
+    }
+
+    /// Makes a PATCH request.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Request path relative to the root URL
+    /// * `params` - Optional query parameters
+    /// * `data` - Optional request body
+    /// * `headers` - Optional additional headers
+    /// * `timeout` - Optional custom timeout for this request
+    /// * `no_retry_on` - Optional list of error kinds that should not trigger retry
+    /// * `context` - Optional context for error reporting
+    pub async fn patch(
+        &self,
+        path: String,
+        params: Option<HashMap<String, String>>,
+        data: Option<String>,
+        headers: Option<HeaderMap>,
+        timeout: Option<u64>,
+        no_retry_on: Option<Vec<ErrorKind>>,
+        context: Option<Context>,
+    ) -> Result<String> {
+        self.do_request(Method::PATCH, path, params, data, headers, timeout, no_retry_on, context)
+            .await
+    }
+
+    /// Makes a DELETE request.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Request path relative to the root URL
+    /// * `params` - Optional query parameters
+    /// * `headers` - Optional additional headers
+    /// * `timeout` - Optional custom timeout for this request
+    /// * `no_retry_on` - Optional list of error kinds that should not trigger retry
+    /// * `context` - Optional context for error reporting
+    pub async fn delete(
+        &self,
+        path: String,
+        params: Option<HashMap<String, String>>,
+        headers: Option<HeaderMap>,
+        timeout: Option<u64>,
+        no_retry_on: Option<Vec<ErrorKind>>,
+        context: Option<Context>,
+    ) -> Result<String> {
+        self.do_request(Method::DELETE, path, params, None, headers, timeout, no_retry_on, context)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Once;
+    use std::time::{Duration, Instant};
+
+    use reqwest::header::{HeaderMap, HeaderValue};
+    use reqwest::Method;
+    use simple_logger::SimpleLogger;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use crate::errors::client::{NETWORK_CONNECTION, REQUEST_ERROR};
+    use crate::errors::http::{SERVICE_UNAVAILABLE, UNPROCESSABLE_ENTITY};
+    use crate::{AsyncBaseClient, AsyncHttpClient, ClientBuilder};
+
+    static INIT: Once = Once::new();
+
+    fn init_logger() {
+        INIT.call_once(|| {
+            let _ = SimpleLogger::new()
+                .with_level(log::LevelFilter::Info)
+                .init();
+        });
+    }
+
+    #[tokio::test]
+    async fn test_no_auth() {
+        init_logger();
+        let cli = AsyncHttpClient::new("https://www.rust-lang.org", None).unwrap();
+        let result = cli.get("/learn/get-started".into(), None, None, None, None, None).await;
+        assert_eq!(result.unwrap().starts_with("<!doctype html>"), true);
+    }
+
+    #[tokio::test]
+    async fn test_err() {
+        init_logger();
+        let cli = AsyncHttpClient::new("https://www.rust-lang.org", None)
+            .unwrap()
+            .set_retry_number(2)
+            .set_retry_delay(1);
+        match cli.get("/sdq".into(), None, None, None, None, None).await {
+            Ok(_) => panic!("No error raised!"),
+            Err(err) => assert_eq!(err.kind, UNPROCESSABLE_ENTITY),
+        };
+    }
+
+    #[tokio::test]
+    async fn test_err_no_retry() {
+        init_logger();
+        let cli = AsyncHttpClient::new("https://www.rust-lang.org", None)
+            .unwrap()
+            .set_retry_number(2)
+            .set_retry_delay(1);
+        match cli
+            .get("/sdq".into(), None, None, None, Some(vec![UNPROCESSABLE_ENTITY]), None)
+            .await
+        {
+            Ok(_) => panic!("No error raised!"),
+            Err(err) => {
+                assert_eq!(err.kind, UNPROCESSABLE_ENTITY);
+                match err.details {
+                    Some(data) => {
+                        match data.get("try") {
+                            Some(value) => {
+                                assert_eq!(serde_value::Value::U64(1), value.clone());
+                            }
+                            None => panic!("No try in error.extra"),
+                        };
+                    }
+                    None => panic!("Not error extra found !"),
+                }
+            }
+        };
+    }
+
+    #[tokio::test]
+    async fn test_network_error_retry() {
+        init_logger();
+        let url = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            format!("http://{}", listener.local_addr().unwrap())
+        };
+        let cli = AsyncHttpClient::new(&url, None)
+            .unwrap()
+            .set_retry_number(3)
+            .set_retry_delay(0);
+        match cli.get("/".into(), None, None, None, None, None).await {
+            Ok(_) => panic!("No error raised!"),
+            Err(err) => {
+                assert_eq!(err.kind, NETWORK_CONNECTION);
+                let details = err.details.expect("No error details found!");
+                assert_eq!(details.get("try"), Some(&serde_value::Value::U64(3)));
+            }
+        };
+    }
+
+    #[tokio::test]
+    async fn test_retry_after() {
+        init_logger();
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/limited"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/limited"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .mount(&server)
+            .await;
+        let cli = AsyncHttpClient::new(&server.uri(), None)
+            .unwrap()
+            .set_retry_number(2)
+            .set_retry_delay(0);
+        let start = Instant::now();
+        let result = cli.get("/limited".into(), None, None, None, None, None).await;
+        assert_eq!(result.unwrap(), "ok");
+        assert!(start.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_max_retry_after() {
+        init_logger();
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/limited"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "3600"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/limited"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .mount(&server)
+            .await;
+        let cli = AsyncHttpClient::new(&server.uri(), None)
+            .unwrap()
+            .set_retry_number(2)
+            .set_max_retry_after(0);
+        let start = Instant::now();
+        let result = cli.get("/limited".into(), None, None, None, None, None).await;
+        assert_eq!(result.unwrap(), "ok");
+        assert!(start.elapsed() < Duration::from_secs(30));
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_details() {
+        init_logger();
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/maintenance"))
+            .respond_with(ResponseTemplate::new(503).insert_header("Retry-After", "120"))
+            .mount(&server)
+            .await;
+        let cli = AsyncHttpClient::new(&server.uri(), None).unwrap();
+        let no_retry_on = Some(vec![SERVICE_UNAVAILABLE]);
+        match cli.get("/maintenance".into(), None, None, None, no_retry_on, None).await {
+            Ok(_) => panic!("No error raised!"),
+            Err(err) => {
+                assert_eq!(err.kind, SERVICE_UNAVAILABLE);
+                let details = err.details.expect("No error details found!");
+                assert_eq!(details.get("retry_after"), Some(&serde_value::Value::U64(120)));
+            }
+        };
+    }
+
+    /// Starts a server failing every `POST /orders` request.
+    async fn failing_orders() -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/orders"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    /// Returns the `Idempotency-Key` headers received by the server.
+    async fn idempotency_keys(server: &MockServer) -> Vec<Option<String>> {
+        server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|req| {
+                req.headers
+                    .get("idempotency-key")
+                    .map(|value| value.to_str().unwrap().to_string())
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_post_not_retried() {
+        init_logger();
+        let server = failing_orders().await;
+        let cli = AsyncHttpClient::new(&server.uri(), None)
+            .unwrap()
+            .set_retry_number(3)
+            .set_retry_delay(0);
+        let result = cli.post("/orders".into(), None, Some("{}".into()), None, None, None, None).await;
+        assert!(result.is_err());
+        assert_eq!(idempotency_keys(&server).await, vec![None]);
+    }
+
+    #[tokio::test]
+    async fn test_post_retry_non_idempotent() {
+        init_logger();
+        let server = failing_orders().await;
+        let cli = AsyncHttpClient::new(&server.uri(), None)
+            .unwrap()
+            .set_retry_number(3)
+            .set_retry_delay(0)
+            .set_retry_non_idempotent(true);
+        let result = cli.post("/orders".into(), None, Some("{}".into()), None, None, None, None).await;
+        assert!(result.is_err());
+        assert_eq!(idempotency_keys(&server).await, vec![None, None, None]);
+    }
+
+    #[tokio::test]
+    async fn test_post_auto_idempotency_key() {
+        init_logger();
+        let server = failing_orders().await;
+        let cli = AsyncHttpClient::new(&server.uri(), None)
+            .unwrap()
+            .set_retry_number(3)
+            .set_retry_delay(0)
+            .set_auto_idempotency_key(true);
+        let result = cli.post("/orders".into(), None, Some("{}".into()), None, None, None, None).await;
+        assert!(result.is_err());
+        let keys = idempotency_keys(&server).await;
+        assert_eq!(keys.len(), 3);
+        assert_eq!(keys[0].as_ref().map(|key| key.len()), Some(36));
+        assert!(keys.iter().all(|key| key == &keys[0]));
+
+        let result = cli.post("/orders".into(), None, Some("{}".into()), None, None, None, None).await;
+        assert!(result.is_err());
+        assert_ne!(idempotency_keys(&server).await[3], keys[0]);
+    }
+
+    #[tokio::test]
+    async fn test_post_given_idempotency_key() {
+        init_logger();
+        let server = failing_orders().await;
+        let cli = AsyncHttpClient::new(&server.uri(), None)
+            .unwrap()
+            .set_retry_number(2)
+            .set_retry_delay(0);
+        let mut headers = HeaderMap::new();
+        headers.insert("idempotency-key", HeaderValue::from_static("order-42"));
+        let result = cli
+            .post("/orders".into(), None, Some("{}".into()), Some(headers), None, None, None)
+            .await;
+        assert!(result.is_err());
+        let expected = Some("order-42".to_string());
+        assert_eq!(idempotency_keys(&server).await, vec![expected.clone(), expected]);
+    }
+
+    #[tokio::test]
+    async fn test_post_request_overrides() {
+        init_logger();
+        let server = failing_orders().await;
+        let cli = AsyncHttpClient::new(&server.uri(), None)
+            .unwrap()
+            .set_retry_number(3)
+            .set_retry_delay(0);
+        let result = cli.request(Method::POST, "/orders").retry_non_idempotent(true).send().await;
+        assert!(result.is_err());
+        assert_eq!(idempotency_keys(&server).await, vec![None, None, None]);
+
+        let server = failing_orders().await;
+        let cli = AsyncHttpClient::new(&server.uri(), None)
+            .unwrap()
+            .set_retry_number(3)
+            .set_retry_delay(0)
+            .set_retry_non_idempotent(true);
+        let result = cli.request(Method::POST, "/orders").retry_non_idempotent(false).send().await;
+        assert!(result.is_err());
+        assert_eq!(idempotency_keys(&server).await, vec![None]);
+
+        let server = failing_orders().await;
+        let cli = AsyncHttpClient::new(&server.uri(), None)
+            .unwrap()
+            .set_retry_number(2)
+            .set_retry_delay(0);
+        let result = cli.request(Method::POST, "/orders").idempotency_key(true).send().await;
+        assert!(result.is_err());
+        let keys = idempotency_keys(&server).await;
+        assert_eq!(keys.len(), 2);
+        assert!(keys[0].is_some() && keys[0] == keys[1]);
+
+        let server = failing_orders().await;
+        let cli = AsyncHttpClient::new(&server.uri(), None)
+            .unwrap()
+            .set_retry_number(2)
+            .set_retry_delay(0)
+            .set_auto_idempotency_key(true);
+        let result = cli.request(Method::POST, "/orders").idempotency_key(false).send().await;
+        assert!(result.is_err());
+        assert_eq!(idempotency_keys(&server).await, vec![None]);
+    }
+
+    #[tokio::test]
+    async fn test_options() {
+        init_logger();
+        let server = MockServer::start().await;
+        Mock::given(method("OPTIONS"))
+            .and(path("/users"))
+            .respond_with(ResponseTemplate::new(204).insert_header("allow", "GET, POST,OPTIONS, ,PROPFIND"))
+            .mount(&server)
+            .await;
+        let cli = AsyncHttpClient::new(&server.uri(), None).unwrap();
+        let methods = cli.options("/users".into(), None, None, None, None, None).await.unwrap();
+        let expected = vec![Method::GET, Method::POST, Method::OPTIONS, Method::from_bytes(b"PROPFIND").unwrap()];
+        assert_eq!(methods, expected);
+    }
+
+    #[tokio::test]
+    async fn test_custom_request() {
+        init_logger();
+        let server = MockServer::start().await;
+        Mock::given(method("PROPFIND"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PROPFIND"))
+            .and(path("/files"))
+            .respond_with(ResponseTemplate::new(207).set_body_string("<multistatus/>"))
+            .mount(&server)
+            .await;
+        let cli = AsyncHttpClient::new(&server.uri(), None)
+            .unwrap()
+            .set_retry_number(2)
+            .set_retry_delay(0);
+        let result = cli.custom_request("PROPFIND", "/files").send().await;
+        assert_eq!(result.unwrap(), "<multistatus/>");
+        let err = cli.custom_request("BAD METHOD", "/files").send().await.unwrap_err();
+        assert_eq!(err.kind, REQUEST_ERROR);
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+}
+
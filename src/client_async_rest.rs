@@ -0,0 +1,718 @@
+/*!
+# Async REST Client Module
+
+This module provides the non-blocking counterpart of the [`RestClient`](crate::RestClient). It handles JSON
+serialization/deserialization exactly like the blocking client, but its request methods return futures and
+retry delays never block the executor.
+
+## Features
+
+- Automatic JSON serialization/deserialization, or any other [`Codec`](crate::codec::Codec)
+- Type-safe request and response handling
+- Default JSON content type headers
+- Same configuration surface, retry semantics and error mapping as the blocking client
+
+## Examples
+
+### Basic Usage
+
+```rust
+use cdumay_http_client::{AsyncRestClient, ClientBuilder};
+use serde::{Deserialize, Serialize};
+use cdumay_error::Result;
+
+#[derive(Serialize, Deserialize)]
+struct User {
+    id: u64,
+    name: String,
+}
+
+#[derive(Serialize, Debug)]
+struct CreateUser {
+    name: String,
+}
+
+async fn create_user() -> Result<User> {
+    let client = AsyncRestClient::new("https://api.example.com", None)?
+        .set_timeout(30)
+        .set_retry_number(3);
+
+    client.post(
+        "/users".to_string(),
+        None,
+        Some(CreateUser { name: "John Doe".to_string() }),
+        None,
+        None,
+        None,
+        None,
+    ).await
+}
+```
+*/
+
+use crate::authentication::Authentication;
+use crate::codec::{self, Codec, JsonCodec};
+use crate::client_rest::ErrorBody;
+use crate::errors::client::{InvalidHeaderValue, InvalidUrl};
+use crate::errors::http_resp_error;
+use crate::retry::{ConstantBackoff, RetryPolicy};
+use crate::request::HttpRequest;
+use crate::tls::TlsConfig;
+use crate::utils::get_or_try_init;
+use crate::{AsyncBaseClient, BodyEncoding, ClientBuilder};
+use cdumay_context::Context;
+use cdumay_error::{Error, ErrorKind, Result};
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, CONTENT_TYPE, USER_AGENT};
+use reqwest::{Method, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_value::Value;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::ops::Deref;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// An asynchronous REST client that handles JSON serialization/deserialization.
+///
+/// This is the non-blocking counterpart of [`RestClient`](crate::RestClient), configured through the same
+/// [`ClientBuilder`] trait.
+///
+/// # Examples
+///
+/// ```rust
+/// use cdumay_http_client::{AsyncRestClient, ClientBuilder};
+/// use serde::{Deserialize, Serialize};
+/// use cdumay_error::Result;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct User {
+///     name: String,
+///     email: String,
+/// }
+///
+/// async fn fetch_user() -> Result<User> {
+///     let client = AsyncRestClient::new("https://api.example.com", None)?;
+///     client.get("/users/123".to_string(), None, None, None, None, None).await
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsyncRestClient {
+    url_root: Url,
+    timeout: u64,
+    headers: HeaderMap,
+    auth: Option<Box<dyn Authentication>>,
+    ssl_verify: bool,
+    tls_config: TlsConfig,
+    retry_number: u64,
+    retry_policy: Box<dyn RetryPolicy>,
+    max_retry_after: u64,
+    retry_non_idempotent: bool,
+    auto_idempotency_key: bool,
+    pool_idle_timeout: Option<u64>,
+    pool_max_idle_per_host: usize,
+    body_encoding: BodyEncoding,
+    codecs: Vec<Box<dyn Codec>>,
+    error_body: Option<ErrorBody>,
+    transport: OnceLock<reqwest::Client>,
+}
+
+impl ClientBuilder for AsyncRestClient {
+    /// Creates a new async REST client with the specified root URL.
+    ///
+    /// This method initializes a REST client with the same defaults as
+    /// [`RestClient::new`](crate::RestClient):
+    /// - Content-Type: application/json
+    /// - Accept: application/json
+    /// - Timeout: 10 seconds
+    /// - Retry attempts: 10
+    /// - Retry delay: 30 seconds
+    /// - SSL verification: enabled
+    /// - Idle pooled connections kept for 90 seconds
+    fn new(url_root: &str, context: Option<&mut Context>) -> Result<AsyncRestClient> {
+        Ok(AsyncRestClient {
+            url_root: Url::parse(url_root.trim_end_matches("/")).map_err(|err| {
+                InvalidUrl::new()
+                    .set_message(format!("Failed to parse URL: {:?}", err))
+                    .set_details({
+                        let mut err_context = Context::new();
+                        if let Some(ctx) = context {
+                            err_context.extend(ctx.deref().clone().into());
+                        };
+                        err_context.insert("url".to_string(), Value::String(url_root.to_string()));
+                        err_context.into()
+                    })
+            })?,
+            timeout: 10,
+            headers: {
+                let mut headers = HeaderMap::new();
+                headers.append(
+                    USER_AGENT,
+                    HeaderValue::from_str(&format!(
+                        "{}/{}",
+                        env!("CARGO_PKG_NAME"),
+                        env!("CARGO_PKG_VERSION")
+                    ))
+                    .map_err(|err| InvalidHeaderValue::new().set_message(err.to_string()))?,
+                );
+                headers.append(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+                headers.append(ACCEPT, HeaderValue::from_static("application/json"));
+                headers
+            },
+            auth: None,
+            ssl_verify: true,
+            tls_config: TlsConfig::default(),
+            retry_number: 10,
+            retry_policy: Box::new(ConstantBackoff::new(Duration::from_secs(30))),
+            max_retry_after: 120,
+            retry_non_idempotent: false,
+            auto_idempotency_key: false,
+            pool_idle_timeout: Some(90),
+            pool_max_idle_per_host: usize::MAX,
+            body_encoding: BodyEncoding::Codec,
+            codecs: vec![Box::new(JsonCodec)],
+            error_body: None,
+            transport: OnceLock::new(),
+        })
+    }
+
+    /// Sets the request timeout in seconds.
+    fn set_timeout(mut self, timeout: u64) -> AsyncRestClient {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets custom headers for all requests.
+    fn set_headers(mut self, headers: HeaderMap) -> AsyncRestClient {
+        self.headers.extend(headers);
+        self
+    }
+
+    /// Sets the authentication method for all requests.
+    fn set_auth<A: Authentication + 'static>(mut self, auth: A) -> AsyncRestClient {
+        self.auth = Some(Box::new(auth));
+        self
+    }
+
+    /// Enables or disables SSL certificate verification.
+    fn set_ssl_verify(mut self, ssl_verify: bool) -> AsyncRestClient {
+        self.ssl_verify = ssl_verify;
+        self.transport = OnceLock::new();
+        self
+    }
+
+    /// Sets the TLS configuration used for all connections.
+    fn set_tls_config(mut self, tls_config: TlsConfig) -> AsyncRestClient {
+        self.tls_config = tls_config;
+        self.transport = OnceLock::new();
+        self
+    }
+
+    /// Sets the number of retry attempts for failed requests.
+    fn set_retry_number(mut self, try_number: u64) -> AsyncRestClient {
+        if try_number == 0 {
+            panic!("Try number MUST be > 0 !");
+        }
+        self.retry_number = try_number;
+        self
+    }
+
+    /// Sets the delay between retry attempts in seconds.
+    fn set_retry_delay(mut self, retry_delay: u64) -> AsyncRestClient {
+        self.retry_policy = Box::new(ConstantBackoff::new(Duration::from_secs(retry_delay)));
+        self
+    }
+
+    fn set_retry_policy<P: RetryPolicy + 'static>(mut self, policy: P) -> AsyncRestClient {
+        self.retry_policy = Box::new(policy);
+        self
+    }
+
+    fn set_max_retry_after(mut self, max_retry_after: u64) -> AsyncRestClient {
+        self.max_retry_after = max_retry_after;
+        self
+    }
+
+    fn set_retry_non_idempotent(mut self, retry_non_idempotent: bool) -> AsyncRestClient {
+        self.retry_non_idempotent = retry_non_idempotent;
+        self
+    }
+
+    fn set_auto_idempotency_key(mut self, auto_idempotency_key: bool) -> AsyncRestClient {
+        self.auto_idempotency_key = auto_idempotency_key;
+        self
+    }
+
+    /// Sets how long, in seconds, idle pooled connections are kept alive.
+    fn set_pool_idle_timeout(mut self, idle_timeout: Option<u64>) -> AsyncRestClient {
+        self.pool_idle_timeout = idle_timeout;
+        self.transport = OnceLock::new();
+        self
+    }
+
+    /// Sets the maximum number of idle pooled connections per host.
+    fn set_pool_max_idle_per_host(mut self, max_idle: usize) -> AsyncRestClient {
+        self.pool_max_idle_per_host = max_idle;
+        self.transport = OnceLock::new();
+        self
+    }
+}
+
+impl AsyncBaseClient for AsyncRestClient {
+    fn url_root(&self) -> &Url {
+        &self.url_root
+    }
+
+    fn timeout(&self) -> &u64 {
+        &self.timeout
+    }
+
+    fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    fn auth(&self) -> Option<&Box<dyn Authentication>> {
+        self.auth.as_ref()
+    }
+
+    fn ssl_verify(&self) -> bool {
+        self.ssl_verify
+    }
+
+    fn tls_config(&self) -> &TlsConfig {
+        &self.tls_config
+    }
+
+    fn retry_number(&self) -> u64 {
+        self.retry_number
+    }
+
+    fn retry_policy(&self) -> &dyn RetryPolicy {
+        self.retry_policy.as_ref()
+    }
+
+    fn max_retry_after(&self) -> u64 {
+        self.max_retry_after
+    }
+
+    fn retry_non_idempotent(&self) -> bool {
+        self.retry_non_idempotent
+    }
+
+    fn auto_idempotency_key(&self) -> bool {
+        self.auto_idempotency_key
+    }
+
+    fn pool_idle_timeout(&self) -> Option<u64> {
+        self.pool_idle_timeout
+    }
+
+    fn pool_max_idle_per_host(&self) -> usize {
+        self.pool_max_idle_per_host
+    }
+
+    fn transport(&self) -> Result<reqwest::Client> {
+        get_or_try_init(&self.transport, || self.build_transport())
+    }
+
+    fn response_error(&self, status: StatusCode, headers: &HeaderMap, body: &[u8], context: Option<Context>) -> Error {
+        let error = http_resp_error(status, headers, body, context);
+        match &self.error_body {
+            Some(error_body) => error_body.apply(&self.codecs, headers, body, error),
+            None => error,
+        }
+    }
+}
+
+impl AsyncRestClient {
+    /// Creates a context object for error reporting.
+    pub(crate) fn create_context(&self, path: String, method: Method) -> Context {
+        let mut context = Context::default();
+        context.insert("server".into(), Value::String(self.url_root.to_string()));
+        context.insert("path".into(), Value::String(path));
+        context.insert("method".into(), Value::String(method.to_string()));
+        context
+    }
+
+    /// Sets the encoding of the bodies sent by [`post`](AsyncRestClient::post) and
+    /// [`put`](AsyncRestClient::put), and the default `Content-Type` header accordingly.
+    ///
+    /// The request builder can still choose the encoding of each request, with
+    /// [`json`](HttpRequest::json) or [`form`](HttpRequest::form).
+    pub fn set_body_encoding(mut self, body_encoding: BodyEncoding) -> AsyncRestClient {
+        self.body_encoding = body_encoding;
+        self.headers.insert(CONTENT_TYPE, body_encoding.content_type(self.codec()));
+        self
+    }
+
+    /// Returns the encoding of the request bodies.
+    pub fn body_encoding(&self) -> BodyEncoding {
+        self.body_encoding
+    }
+
+    /// Sets the codec encoding the request bodies and decoding the responses, [`JsonCodec`] by default.
+    ///
+    /// See [`RestClient::set_codec`](crate::RestClient::set_codec).
+    pub fn set_codec<C: Codec + 'static>(mut self, codec: C) -> AsyncRestClient {
+        self.codecs[0] = Box::new(codec);
+        if self.body_encoding == BodyEncoding::Codec {
+            self.headers.insert(CONTENT_TYPE, self.codecs[0].content_type());
+        }
+        self.headers.insert(ACCEPT, codec::accept(&self.codecs));
+        self
+    }
+
+    /// Adds a codec decoding the responses of its media types, and adds them to the `Accept` header.
+    pub fn add_codec<C: Codec + 'static>(mut self, codec: C) -> AsyncRestClient {
+        self.codecs.push(Box::new(codec));
+        self.headers.insert(ACCEPT, codec::accept(&self.codecs));
+        self
+    }
+
+    /// Returns the codec encoding the request bodies.
+    pub fn codec(&self) -> &dyn Codec {
+        self.codecs[0].as_ref()
+    }
+
+    /// Returns the codecs decoding the responses, starting with the main one.
+    pub(crate) fn codecs(&self) -> &[Box<dyn Codec>] {
+        &self.codecs
+    }
+
+    /// Sets how the error bodies of an API with its own error envelope are read; see
+    /// [`RestClient::set_error_body`](crate::RestClient::set_error_body).
+    pub fn set_error_body<E, F>(mut self, hook: F) -> AsyncRestClient
+    where
+        E: DeserializeOwned,
+        F: Fn(E, Error) -> Error + Send + Sync + 'static,
+    {
+        self.error_body = Some(ErrorBody::new(hook));
+        self
+    }
+
+    /// Starts building a request to `path`, relative to the root URL.
+    ///
+    /// The response is decoded by the client codecs when the request is sent; see [`HttpRequest`].
+    pub fn request<P: Into<String>>(&self, method: Method, path: P) -> HttpRequest<'_, AsyncRestClient> {
+        HttpRequest::new(self, method, path)
+    }
+
+    /// Starts building a request using a method given by name, such as the WebDAV `PROPFIND` or `MKCOL`.
+    ///
+    /// An invalid method name is reported as a [`RequestError`](crate::errors::client::RequestError) when
+    /// the request is sent.
+    pub fn custom_request<P: Into<String>>(&self, method: &str, path: P) -> HttpRequest<'_, AsyncRestClient> {
+        HttpRequest::custom(self, method, path)
+    }
+
+    /// Makes a GET request and deserializes the JSON response.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Request path relative to the root URL
+    /// * `params` - Optional query parameters
+    /// * `headers` - Optional additional headers
+    /// * `timeout` - Optional custom timeout for this request
+    /// * `no_retry_on` - Optional list of error kinds that should not trigger retry
+    /// * `context` - Optional context for error reporting
+    pub async fn get<R>(
+        &self,
+        path: String,
+        params: Option<HashMap<String, String>>,
+        headers: Option<HeaderMap>,
+        timeout: Option<u64>,
+        no_retry_on: Option<Vec<ErrorKind>>,
+        context: Option<Context>,
+    ) -> Result<R>
+    where
+        R: DeserializeOwned,
+    {
+        HttpRequest {
+            params,
+            headers,
+            timeout,
+            no_retry_on,
+            context,
+            ..self.request(Method::GET, path)
+        }
+        .send().await
+    }
+
+    /// Makes a POST request with an optional body and deserializes the JSON response.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Request path relative to the root URL
+    /// * `params` - Optional query parameters
+    /// * `data` - Optional request body to serialize as JSON
+    /// * `headers` - Optional additional headers
+    /// * `timeout` - Optional custom timeout for this request
+    /// * `no_retry_on` - Optional list of error kinds that should not trigger retry
+    /// * `context` - Optional context for error reporting
+    pub async fn post<D, R>(
+        &self,
+        path: String,
+        params: Option<HashMap<String, String>>,
+        data: Option<D>,
+        headers: Option<HeaderMap>,
+        timeout: Option<u64>,
+        no_retry_on: Option<Vec<ErrorKind>>,
+        context: Option<Context>,
+    ) -> Result<R>
+    where
+        D: Serialize + Debug,
+        R: DeserializeOwned,
+    {
+        let request = HttpRequest {
+            params,
+            headers,
+            timeout,
+            no_retry_on,
+            context,
+            ..self.request(Method::POST, path)
+        };
+        match data {
+            Some(data) => request.serialize(&data),
+            None => request,
+        }
+        .send().await
+    }
+
+    /// Makes a PUT request with an optional body and deserializes the JSON response.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Request path relative to the root URL
+    /// * `params` - Optional query parameters
+    /// * `data` - Optional request body to serialize as JSON
+    /// * `headers` - Optional additional headers
+    /// * `timeout` - Optional custom timeout for this request
+    /// * `no_retry_on` - Optional list of error kinds that should not trigger retry
+    /// * `context` - Optional context for error reporting
+    pub async fn put<D, R>(
+        &self,
+        path: String,
+        params: Option<HashMap<String, String>>,
+        data: Option<D>,
+        headers: Option<HeaderMap>,
+        timeout: Option<u64>,
+        no_retry_on: Option<Vec<ErrorKind>>,
+        context: Option<Context>,
+    ) -> Result<R>
+    where
+        D: Serialize + Debug,
+        R: DeserializeOwned,
+    {
+        let request = HttpRequest {
+            params,
+            headers,
+            timeout,
+            no_retry_on,
+            context,
+            ..self.request(Method::PUT, path)
+        };
+        match data {
+            Some(data) => request.serialize(&data),
+            None => request,
+        }
+        .send().await
+    }
+
+    /// Makes a PATCH request with an optional body and deserializes the JSON response.
+    ///
+    /// The body is encoded by the client codec; use [`request`](AsyncRestClient::request) with
+    /// [`merge_patch`](HttpRequest::merge_patch) or [`json_patch`](HttpRequest::json_patch) to send a
+    /// JSON Merge Patch or a JSON Patch document.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Request path relative to the root URL
+    /// * `params` - Optional query parameters
+    /// * `data` - Optional request body to serialize as JSON
+    /// * `headers` - Optional additional headers
+    /// * `timeout` - Optional custom timeout for this request
+    /// * `no_retry_on` - Optional list of error kinds that should not trigger retry
+    /// * `context` - Optional context for error reporting
+    pub async fn patch<D, R>(
+        &self,
+        path: String,
+        params: Option<HashMap<String, String>>,
+        data: Option<D>,
+        headers: Option<HeaderMap>,
+        timeout: Option<u64>,
+        no_retry_on: Option<Vec<ErrorKind>>,
+        context: Option<Context>,
+    ) -> Result<R>
+    where
+        D: Serialize + Debug,
+        R: DeserializeOwned,
+    {
+        let request = HttpRequest {
+            params,
+            headers,
+            timeout,
+            no_retry_on,
+            context,
+            ..self.request(Method::PATCH, path)
+        };
+        match data {
+            Some(data) => request.serialize(&data),
+            None => request,
+        }
+        .send().await
+    }
+
+    /// Makes a DELETE request and deserializes the JSON response.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Request path relative to the root URL
+    /// * `params` - Optional query parameters
+    /// * `headers` - Optional additional headers
+    /// * `timeout` - Optional custom timeout for this request
+    /// * `no_retry_on` - Optional list of error kinds that should not trigger retry
+    /// * `context` - Optional context for error reporting
+    pub async fn delete<R>(
+        &self,
+        path: String,
+        params: Option<HashMap<String, String>>,
+        headers: Option<HeaderMap>,
+        timeout: Option<u64>,
+        no_retry_on: Option<Vec<ErrorKind>>,
+        context: Option<Context>,
+    ) -> Result<R>
+    where
+        R: DeserializeOwned,
+    {
+        HttpRequest {
+            params,
+            headers,
+            timeout,
+            no_retry_on,
+            context,
+            ..self.request(Method::DELETE, path)
+        }
+        .send().await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::client_async_rest::AsyncRestClient;
+    use crate::errors::http::NOT_FOUND;
+    use crate::errors::rest::DataError;
+    use crate::ClientBuilder;
+    use cdumay_error::Error;
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+    use serde_value::Value;
+    use simple_logger::SimpleLogger;
+    use std::sync::Once;
+    use wiremock::matchers::path;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    static INIT: Once = Once::new();
+
+    fn init_logger() {
+        INIT.call_once(|| {
+            let _ = SimpleLogger::new()
+                .with_level(log::LevelFilter::Info)
+                .init();
+        });
+    }
+    #[derive(Serialize, Deserialize, Clone, Debug)]
+    struct Todo {
+        id: usize,
+        todo: String,
+        completed: bool,
+        #[serde(rename(deserialize = "userId"))]
+        user_id: u64,
+    }
+    #[derive(Serialize, Deserialize, Clone, Debug)]
+    struct Foo {
+        id: usize,
+        foo: String,
+    }
+
+    #[tokio::test]
+    async fn test_get() {
+        init_logger();
+        let cli = AsyncRestClient::new("https://dummyjson.com", None).unwrap();
+        let result = cli.get::<Todo>("/todos/1".into(), None, None, None, None, None).await;
+        match result {
+            Ok(todo) => assert_eq!(todo.user_id, 152),
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_payload_error() {
+        init_logger();
+        let cli = AsyncRestClient::new("https://dummyjson.com", None)
+            .unwrap()
+            .set_retry_number(2)
+            .set_retry_delay(1);
+        let result = cli.get::<Foo>("/todos/1".into(), None, None, None, None, None).await;
+        match result {
+            Ok(_) => panic!("No error raised!"),
+            Err(err) => assert_eq!(err.kind, DataError),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_response_error() {
+        init_logger();
+        let cli = AsyncRestClient::new("https://dummyjson.com", None)
+            .unwrap()
+            .set_retry_number(2)
+            .set_retry_delay(1);
+        let result = cli
+            .get::<Todo>("/todos/a".into(), None, None, None, Some(vec![NOT_FOUND]), None)
+            .await;
+        match result {
+            Ok(_) => panic!("No error raised!"),
+            Err(err) => assert_eq!(err.kind, NOT_FOUND),
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct ApiError {
+        code: String,
+        message: String,
+    }
+
+    #[tokio::test]
+    async fn test_error_body() {
+        let server = MockServer::start().await;
+        Mock::given(path("/todos/1"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(json!({"code": "TODO-404", "message": "No todo 1"})))
+            .mount(&server)
+            .await;
+        Mock::given(path("/todos/2"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("Not Found"))
+            .mount(&server)
+            .await;
+        let cli = AsyncRestClient::new(&server.uri(), None)
+            .unwrap()
+            .set_error_body(|body: ApiError, mut err: Error| {
+                err.message = body.message;
+                err.details
+                    .get_or_insert_with(Default::default)
+                    .insert("code".into(), Value::String(body.code));
+                err
+            });
+        let err = cli
+            .get::<Todo>("/todos/1".into(), None, None, None, Some(vec![NOT_FOUND]), None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind, NOT_FOUND);
+        assert_eq!(err.message, "No todo 1");
+        assert_eq!(err.details.unwrap()["code"], Value::String("TODO-404".into()));
+
+        let err = cli
+            .get::<Todo>("/todos/2".into(), None, None, None, Some(vec![NOT_FOUND]), None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.message, "Not Found");
+    }
+}
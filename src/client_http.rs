@@ -6,7 +6,8 @@ This module provides a robust and feature-rich HTTP client implementation with s
 - Custom authentication
 - Request/response header management
 - Timeout configuration
-- SSL verification
+- SSL verification and TLS configuration
+- Connection pooling across requests
 - Error handling with detailed context
 
 ## Features
@@ -139,24 +140,48 @@ let auth = BasicAuth::new(
 let client = HttpClient::new("https://api.example.com", None).unwrap()
     .set_auth(auth);
 ```
+
+### TLS Configuration
+
+```rust,no_run
+use cdumay_http_client::{ClientBuilder, HttpClient};
+use cdumay_http_client::tls::{TlsConfig, Version};
+
+let tls = TlsConfig::new()
+    .add_root_certificate_file("/etc/ssl/private-ca.pem").unwrap()
+    .set_min_tls_version(Version::TLS_1_2);
+
+let client = HttpClient::new("https://api.example.com", None).unwrap()
+    .set_tls_config(tls);
+```
 */
 
 use cdumay_context::Context;
 use cdumay_error::{Error, ErrorKind, Result};
 use chrono::Utc;
 use reqwest::blocking::{Client, RequestBuilder, Response};
-use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
-use reqwest::{Method, Url};
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE, USER_AGENT};
+use reqwest::{Method, StatusCode, Url};
 use serde_value::Value;
 use std::collections::HashMap;
+use std::io::Write;
 use std::ops::Deref;
+use std::path::Path;
+use std::sync::OnceLock;
 use std::thread;
 use std::time::Duration;
 
 use crate::authentication::Authentication;
-use crate::errors::client::{ClientBuilderError, InvalidHeaderValue, InvalidUrl};
-use crate::errors::{http_error_serialize, http_resp_serialise};
-use crate::utils::{build_url, merge_headers};
+use crate::body::Body;
+use crate::errors::client::{InvalidHeaderValue, InvalidUrl};
+use crate::errors::{http_error_serialize, http_resp_error};
+use crate::retry::{ConstantBackoff, RetryPolicy};
+use crate::request::{HttpRequest, RequestOptions};
+use crate::response::HttpResponse;
+use crate::tls::TlsConfig;
+use crate::utils::{
+    authentication, build_url, get_or_try_init, request_headers, retry_number, strip_auth_query, Attempts, Outcome,
+};
 
 /// Trait for building HTTP clients with configurable settings.
 ///
@@ -191,11 +216,48 @@ pub trait ClientBuilder {
     /// Enables or disables SSL certificate verification.
     fn set_ssl_verify(self, ssl_verify: bool) -> Self;
 
+    /// Sets the TLS configuration (root certificates, minimum version, hostname verification).
+    fn set_tls_config(self, tls_config: TlsConfig) -> Self;
+
     /// Sets the number of retry attempts for failed requests.
     fn set_retry_number(self, retry_number: u64) -> Self;
 
-    /// Sets the delay between retry attempts in seconds.
+    /// Sets a constant delay between retry attempts in seconds.
+    ///
+    /// This is a shortcut for [`set_retry_policy`](ClientBuilder::set_retry_policy) with a
+    /// [`ConstantBackoff`].
     fn set_retry_delay(self, retry_delay: u64) -> Self;
+
+    /// Sets the policy deciding whether and when a failed request is retried.
+    fn set_retry_policy<P: RetryPolicy + 'static>(self, policy: P) -> Self;
+
+    /// Sets the maximum delay, in seconds, honoured from a `Retry-After` header (default: 120).
+    ///
+    /// When a `429 Too Many Requests` or `503 Service Unavailable` response carries a `Retry-After`
+    /// header, the next attempt waits the requested delay, bounded by this maximum, instead of the
+    /// delay given by the retry policy.
+    fn set_max_retry_after(self, max_retry_after: u64) -> Self;
+
+    /// Enables or disables retries of requests using non-idempotent methods (default: disabled).
+    ///
+    /// `GET`, `HEAD`, `PUT`, `DELETE`, `OPTIONS` and `TRACE` requests are always retried. Other
+    /// requests, like `POST` and `PATCH`, are sent only once unless this option is enabled or the
+    /// request carries an `Idempotency-Key` header.
+    fn set_retry_non_idempotent(self, retry_non_idempotent: bool) -> Self;
+
+    /// Enables or disables the generation of an `Idempotency-Key` header for `POST` and `PATCH`
+    /// requests (default: disabled).
+    ///
+    /// The key is generated once per call and sent with every attempt, so that the server can
+    /// deduplicate retried requests; the requests carrying it are therefore retried. A key given in
+    /// the request headers is kept as is.
+    fn set_auto_idempotency_key(self, auto_idempotency_key: bool) -> Self;
+
+    /// Sets how long, in seconds, idle pooled connections are kept alive (`None` keeps them forever).
+    fn set_pool_idle_timeout(self, idle_timeout: Option<u64>) -> Self;
+
+    /// Sets the maximum number of idle pooled connections kept per host.
+    fn set_pool_max_idle_per_host(self, max_idle: usize) -> Self;
 }
 
 /// Base trait for HTTP client implementations.
@@ -218,15 +280,61 @@ pub trait BaseClient {
     /// Returns whether SSL verification is enabled.
     fn ssl_verify(&self) -> bool;
 
+    /// Returns the TLS configuration.
+    fn tls_config(&self) -> &TlsConfig;
+
     /// Returns the number of retry attempts for failed requests.
     fn retry_number(&self) -> u64;
 
-    /// Returns the delay between retry attempts in seconds.
-    fn retry_delay(&self) -> u64;
+    /// Returns the policy deciding whether and when a failed request is retried.
+    fn retry_policy(&self) -> &dyn RetryPolicy;
+
+    /// Returns the maximum delay, in seconds, honoured from a `Retry-After` header.
+    fn max_retry_after(&self) -> u64;
+
+    /// Returns whether requests using non-idempotent methods (`POST`, `PATCH`...) are retried.
+    fn retry_non_idempotent(&self) -> bool;
+
+    /// Returns whether an `Idempotency-Key` header is generated for `POST` and `PATCH` requests.
+    fn auto_idempotency_key(&self) -> bool;
+
+    /// Returns how long, in seconds, idle pooled connections are kept alive.
+    fn pool_idle_timeout(&self) -> Option<u64>;
+
+    /// Returns the maximum number of idle pooled connections per host.
+    fn pool_max_idle_per_host(&self) -> usize;
+
+    /// Returns the pooled transport shared by all the requests of this client.
+    ///
+    /// The transport is built from the client settings on first use and then reused, so that
+    /// connections, TLS sessions and DNS results are kept across requests.
+    fn transport(&self) -> Result<Client>;
+
+    /// Builds a new transport from the TLS and connection pool settings.
+    fn build_transport(&self) -> Result<Client> {
+        let builder = Client::builder()
+            .pool_idle_timeout(self.pool_idle_timeout().map(Duration::from_secs))
+            .pool_max_idle_per_host(self.pool_max_idle_per_host());
+        self.tls_config()
+            .apply(builder, self.ssl_verify())
+            .build()
+            .map_err(|err| http_error_serialize(&err, None))
+    }
 
     /// Internal method to wrap request execution with error handling.
-    fn _request_wrapper(&self, req: RequestBuilder) -> Result<Response> {
-        Ok(req.send().map_err(|err| http_error_serialize(&err, None))?)
+    ///
+    /// Transport failures (connection refused, DNS failure, timeout...) are returned as
+    /// [`NetworkError`](crate::errors::client::NetworkError) and retried by [`BaseClient::execute_raw`].
+    fn _request_wrapper(&self, req: RequestBuilder, context: Option<Context>) -> Result<Response> {
+        Ok(req.send().map_err(|err| http_error_serialize(&err.without_url(), context))?)
+    }
+
+    /// Builds the error of an unsuccessful response from its status, headers and body.
+    ///
+    /// The default implementation parses `application/problem+json` bodies into the error details (see
+    /// [`problem`](crate::errors::problem)); clients override it to map their own error bodies.
+    fn response_error(&self, status: StatusCode, headers: &HeaderMap, body: &[u8], context: Option<Context>) -> Error {
+        http_resp_error(status, headers, body, context)
     }
 
     /// Makes an HTTP request with the specified parameters.
@@ -243,130 +351,171 @@ pub trait BaseClient {
     /// * `method` - HTTP method to use
     /// * `path` - Request path relative to the root URL
     /// * `params` - Optional query parameters
-    /// * `data` - Optional request body
+    /// * `data` - Optional request body, sent again on each attempt
     /// * `headers` - Optional additional headers
     /// * `timeout` - Optional custom timeout for this request
     /// * `no_retry_on` - Optional list of error kinds that should not trigger retry
     /// * `context` - Optional context for error reporting
+    /// * `options` - Optional settings overriding the client configuration for this request
     ///
     /// # Returns
     ///
-    /// Returns `Result<String>` which is:
-    /// - `Ok(String)` containing the response body if successful
+    /// Returns `Result<(Response, u64)>` which is:
+    /// - `Ok((Response, u64))` containing the response, whose body is left unread so that it can be
+    ///   streamed, and the number of attempts, if successful
     /// - `Err` with detailed error information if the request fails
-    fn do_request(
+    ///
+    /// A `304 Not Modified` response, answering a conditional request, is returned like a successful one.
+    ///
+    /// The authentication headers and query parameters are computed for each attempt, after
+    /// [`Authentication::prepare`], whose errors are returned as is. After a `401 Unauthorized`
+    /// response, the request is sent again once without counting as a retry when
+    /// [`Authentication::on_unauthorized`] refreshed the credentials.
+    fn execute_raw(
         &self,
         method: Method,
         path: String,
         params: Option<HashMap<String, String>>,
-        data: Option<String>,
+        data: Option<&Body>,
         headers: Option<HeaderMap>,
         timeout: Option<u64>,
         no_retry_on: Option<Vec<ErrorKind>>,
         context: Option<Context>,
-    ) -> Result<String> {
-        let start = Utc::now();
+        options: Option<RequestOptions>,
+    ) -> Result<(Response, u64)> {
         let url = build_url(self.url_root(), path, params)?;
-        let mut context = context.unwrap_or_default();
-        context.insert("url".into(), Value::String(url.to_string()));
-        context.insert("method".into(), Value::String(method.to_string()));
-        let cli = Client::builder()
-            .timeout(Duration::from_secs(
-                timeout.unwrap_or(self.timeout().clone()),
-            ))
-            .default_headers(merge_headers(self.headers(), headers))
-            .build()
-            .map_err(|err| http_error_serialize(&err, Some(context.clone().into())))?;
-
-        debug!("{} {}", &method, &url.as_str());
-        let mut req = cli.request(method.clone(), url.clone());
-        if let Some(auth) = self.auth() {
-            if let Some((name, value)) = auth.as_header() {
-                req = req.header(name, value);
-            }
-        }
-        if let Some(txt) = data {
-            req = req.body::<String>(txt);
+        let options = options.unwrap_or_default();
+        let mut headers = request_headers(
+            self.headers(),
+            headers,
+            &method,
+            options.idempotency_key.unwrap_or(self.auto_idempotency_key()),
+        );
+        if let Some(content_type) = data.and_then(Body::content_type) {
+            headers.insert(CONTENT_TYPE, content_type);
         }
-        let mut last_error: Option<Error> = None;
-        for req_try in 1..=self.retry_number() {
-            info!("[{}] - {} (try: {})", method, url, req_try);
-            match req.try_clone() {
-                Some(req) => {
-                    let resp = self._request_wrapper(req)?;
-                    let end = { Utc::now() - start }.to_std().unwrap();
-                    let human = humantime::format_duration(end).to_string();
-                    let length = resp.content_length().unwrap_or(0);
-                    match resp.status().is_success() {
-                        true => {
-                            info!(
-                                "{} {} - {} - {} [{}]",
-                                &method,
-                                &url.as_str(),
-                                resp.status(),
-                                length,
-                                &human
-                            );
-                            return Ok(resp.text().map_err(|err| {
-                                http_error_serialize(&err, Some(context.into()))
-                            })?);
-                        }
-                        false => {
-                            error!(
-                                "{} {} - {} - {} [{}]",
-                                &method,
-                                &url.as_str(),
-                                resp.status(),
-                                length,
-                                &human
-                            );
-                            let mut err_context = context.clone();
-                            err_context.insert("try".into(), Value::U64(req_try));
-                            let err = http_resp_serialise(resp, Some(err_context));
-                            if let Some(kinds) = &no_retry_on {
-                                if kinds.contains(&err.kind) {
-                                    return Err(err);
-                                }
-                            }
-                            last_error = Some(err);
+        let mut attempts = Attempts::new(
+            &method,
+            &url,
+            context,
+            no_retry_on,
+            retry_number(
+                &method,
+                &headers,
+                options.retry_non_idempotent.unwrap_or(self.retry_non_idempotent()),
+                options.retry_number.unwrap_or(self.retry_number()),
+            ),
+            self.retry_policy(),
+            self.max_retry_after(),
+        );
+        let req = self
+            .transport()?
+            .request(method.clone(), url.clone())
+            .timeout(Duration::from_secs(timeout.unwrap_or(*self.timeout())))
+            .headers(headers);
+        loop {
+            let err_context = attempts.next();
+            let req = req.try_clone().ok_or_else(|| attempts.clone_error())?;
+            if let Some(auth) = self.auth() {
+                auth.prepare()?;
+            }
+            let (auth_headers, auth_query) = authentication(self.auth().map(|auth| auth.as_ref()), &method, &url);
+            let req = req.headers(auth_headers).query(&auth_query);
+            let req = match data {
+                Some(body) => req.body(body.transport(err_context.clone())?),
+                None => req,
+            };
+            let err = match self._request_wrapper(req, Some(err_context.clone())) {
+                Ok(resp) => {
+                    let auth = self.auth().map(|auth| auth.as_ref());
+                    match attempts.response(resp.status(), resp.headers(), resp.content_length(), auth) {
+                        Outcome::Success => return Ok((resp, attempts.count())),
+                        Outcome::Reauthenticate => continue,
+                        Outcome::Failure => {
+                            let (status, headers) = (resp.status(), resp.headers().clone());
+                            let body = resp.bytes().unwrap_or_default();
+                            self.response_error(status, &headers, &body, Some(err_context))
                         }
-                    };
-                    thread::sleep(Duration::from_secs(self.retry_delay()));
-                }
-                None => {
-                    return Err(ClientBuilderError::new()
-                        .set_message("Internal error, failed to clone request".into())
-                        .set_details(context.into())
-                        .into())
+                    }
                 }
-            }
-        }
-        match last_error {
-            Some(err) => {
-                error!(
-                    "Failed to perform request {} on {} after {} retries : {}",
-                    method,
-                    url,
-                    self.retry_number(),
-                    err
-                );
-                Err(err)
-            }
-            None => {
-                error!(
-                    "Unexpected error, failed to perform request {} on {} after {} retries",
-                    method,
-                    url,
-                    self.retry_number()
-                );
-                Err(ClientBuilderError::new()
-                    .set_message("Internal error, failed to clone request".into())
-                    .set_details(context.into())
-                    .into())
-            }
+                Err(err) => attempts.transport_error(err)?,
+            };
+            thread::sleep(attempts.failed(err)?);
         }
     }
 
+    /// Makes an HTTP request with the specified parameters and reads the whole response.
+    ///
+    /// This method accepts the same arguments as [`execute_raw`](BaseClient::execute_raw).
+    ///
+    /// # Returns
+    ///
+    /// Returns `Result<HttpResponse>` which is:
+    /// - `Ok(HttpResponse)` containing the status, headers and body if successful
+    /// - `Err` with detailed error information if the request fails
+    fn execute(
+        &self,
+        method: Method,
+        path: String,
+        params: Option<HashMap<String, String>>,
+        data: Option<Body>,
+        headers: Option<HeaderMap>,
+        timeout: Option<u64>,
+        no_retry_on: Option<Vec<ErrorKind>>,
+        context: Option<Context>,
+        options: Option<RequestOptions>,
+    ) -> Result<HttpResponse> {
+        let start = Utc::now();
+        let mut context = context.unwrap_or_default();
+        let (resp, attempts) = self.execute_raw(
+            method.clone(),
+            path,
+            params,
+            data.as_ref(),
+            headers,
+            timeout,
+            no_retry_on,
+            Some(context.clone()),
+            options,
+        )?;
+        let status = resp.status();
+        let headers = resp.headers().clone();
+        let url = strip_auth_query(resp.url(), self.auth().map(|auth| auth.as_ref()));
+        context.insert("url".into(), Value::String(url.to_string()));
+        context.insert("method".into(), Value::String(method.to_string()));
+        let body = resp
+            .bytes()
+            .map_err(|err| http_error_serialize(&err, Some(context)))?;
+        let elapsed = { Utc::now() - start }.to_std().unwrap_or_default();
+        Ok(HttpResponse::new(method, status, headers, url, attempts, elapsed, body))
+    }
+
+    /// Makes an HTTP request with the specified parameters and returns the response body.
+    ///
+    /// This is a shortcut for [`execute`](BaseClient::execute) which drops the status and the headers
+    /// of the response; it accepts the same arguments.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Result<String>` which is:
+    /// - `Ok(String)` containing the response body if successful
+    /// - `Err` with detailed error information if the request fails
+    fn do_request(
+        &self,
+        method: Method,
+        path: String,
+        params: Option<HashMap<String, String>>,
+        data: Option<String>,
+        headers: Option<HeaderMap>,
+        timeout: Option<u64>,
+        no_retry_on: Option<Vec<ErrorKind>>,
+        context: Option<Context>,
+    ) -> Result<String> {
+        Ok(self
+            .execute(method, path, params, data.map(Body::from), headers, timeout, no_retry_on, context, None)?
+            .into_text())
+    }
+
     /// Makes an HEAD request with the specified parameters.
     ///
     /// This method handles all the request logic including:
@@ -411,6 +560,37 @@ pub trait BaseClient {
         )?;
         Ok(())
     }
+
+    /// Makes an OPTIONS request, to discover the methods allowed on a resource.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Request path relative to the root URL
+    /// * `params` - Optional query parameters
+    /// * `headers` - Optional additional headers, such as the `Origin` and
+    ///   `Access-Control-Request-Method` headers of a CORS preflight request
+    /// * `timeout` - Optional custom timeout for this request
+    /// * `no_retry_on` - Optional list of error kinds that should not trigger retry
+    /// * `context` - Optional context for error reporting
+    ///
+    /// # Returns
+    ///
+    /// Returns `Result<Vec<Method>>` which is:
+    /// - `Ok(Vec<Method>)` containing the methods listed by the `Allow` header of the response
+    /// - `Err` with detailed error information if the request fails
+    fn options(
+        &self,
+        path: String,
+        params: Option<HashMap<String, String>>,
+        headers: Option<HeaderMap>,
+        timeout: Option<u64>,
+        no_retry_on: Option<Vec<ErrorKind>>,
+        context: Option<Context>,
+    ) -> Result<Vec<Method>> {
+        Ok(self
+            .execute(Method::OPTIONS, path, params, None, headers, timeout, no_retry_on, context, None)?
+            .allowed_methods())
+    }
 }
 
 /// HTTP client implementation with retry capabilities and configurable settings.
@@ -456,8 +636,15 @@ pub struct HttpClient {
     headers: HeaderMap,
     auth: Option<Box<dyn Authentication>>,
     ssl_verify: bool,
+    tls_config: TlsConfig,
     retry_number: u64,
-    retry_delay: u64,
+    retry_policy: Box<dyn RetryPolicy>,
+    max_retry_after: u64,
+    retry_non_idempotent: bool,
+    auto_idempotency_key: bool,
+    pool_idle_timeout: Option<u64>,
+    pool_max_idle_per_host: usize,
+    transport: OnceLock<Client>,
 }
 
 impl ClientBuilder for HttpClient {
@@ -491,8 +678,15 @@ impl ClientBuilder for HttpClient {
             },
             auth: None,
             ssl_verify: true,
+            tls_config: TlsConfig::default(),
             retry_number: 10,
-            retry_delay: 30,
+            retry_policy: Box::new(ConstantBackoff::new(Duration::from_secs(30))),
+            max_retry_after: 120,
+            retry_non_idempotent: false,
+            auto_idempotency_key: false,
+            pool_idle_timeout: Some(90),
+            pool_max_idle_per_host: usize::MAX,
+            transport: OnceLock::new(),
         })
     }
 
@@ -513,6 +707,13 @@ impl ClientBuilder for HttpClient {
 
     fn set_ssl_verify(mut self, ssl_verify: bool) -> Self {
         self.ssl_verify = ssl_verify;
+        self.transport = OnceLock::new();
+        self
+    }
+
+    fn set_tls_config(mut self, tls_config: TlsConfig) -> Self {
+        self.tls_config = tls_config;
+        self.transport = OnceLock::new();
         self
     }
 
@@ -525,7 +726,39 @@ impl ClientBuilder for HttpClient {
     }
 
     fn set_retry_delay(mut self, retry_delay: u64) -> Self {
-        self.retry_delay = retry_delay;
+        self.retry_policy = Box::new(ConstantBackoff::new(Duration::from_secs(retry_delay)));
+        self
+    }
+
+    fn set_retry_policy<P: RetryPolicy + 'static>(mut self, policy: P) -> Self {
+        self.retry_policy = Box::new(policy);
+        self
+    }
+
+    fn set_max_retry_after(mut self, max_retry_after: u64) -> Self {
+        self.max_retry_after = max_retry_after;
+        self
+    }
+
+    fn set_retry_non_idempotent(mut self, retry_non_idempotent: bool) -> Self {
+        self.retry_non_idempotent = retry_non_idempotent;
+        self
+    }
+
+    fn set_auto_idempotency_key(mut self, auto_idempotency_key: bool) -> Self {
+        self.auto_idempotency_key = auto_idempotency_key;
+        self
+    }
+
+    fn set_pool_idle_timeout(mut self, idle_timeout: Option<u64>) -> Self {
+        self.pool_idle_timeout = idle_timeout;
+        self.transport = OnceLock::new();
+        self
+    }
+
+    fn set_pool_max_idle_per_host(mut self, max_idle: usize) -> Self {
+        self.pool_max_idle_per_host = max_idle;
+        self.transport = OnceLock::new();
         self
     }
 }
@@ -551,16 +784,59 @@ impl BaseClient for HttpClient {
         self.ssl_verify
     }
 
+    fn tls_config(&self) -> &TlsConfig {
+        &self.tls_config
+    }
+
     fn retry_number(&self) -> u64 {
         self.retry_number
     }
 
-    fn retry_delay(&self) -> u64 {
-        self.retry_delay
+    fn retry_policy(&self) -> &dyn RetryPolicy {
+        self.retry_policy.as_ref()
+    }
+
+    fn max_retry_after(&self) -> u64 {
+        self.max_retry_after
+    }
+
+    fn retry_non_idempotent(&self) -> bool {
+        self.retry_non_idempotent
+    }
+
+    fn auto_idempotency_key(&self) -> bool {
+        self.auto_idempotency_key
+    }
+
+    fn pool_idle_timeout(&self) -> Option<u64> {
+        self.pool_idle_timeout
+    }
+
+    fn pool_max_idle_per_host(&self) -> usize {
+        self.pool_max_idle_per_host
+    }
+
+    fn transport(&self) -> Result<Client> {
+        get_or_try_init(&self.transport, || self.build_transport())
     }
 }
 
 impl HttpClient {
+    /// Starts building a request to `path`, relative to the root URL.
+    ///
+    /// This is a fluent alternative to the methods below; see [`HttpRequest`].
+    pub fn request<P: Into<String>>(&self, method: Method, path: P) -> HttpRequest<'_, HttpClient> {
+        HttpRequest::new(self, method, path)
+    }
+
+    /// Starts building a request using a method given by name, such as the WebDAV `PROPFIND` or `MKCOL`.
+    ///
+    /// An invalid method name is reported as a [`RequestError`](crate::errors::client::RequestError) when
+    /// the request is sent.
+    pub fn custom_request<P: Into<String>>(&self, method: &str, path: P) -> HttpRequest<'_, HttpClient> {
+        HttpRequest::custom(self, method, path)
+    }
+
     /// Makes a GET request.
     ///
     /// # Arguments
@@ -658,6 +934,91 @@ impl HttpClient {
         )
     }
 
+    /// Makes a PATCH request.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Request path relative to the root URL
+    /// * `params` - Optional query parameters
+    /// * `data` - Optional request body
+    /// * `headers` - Optional additional headers
+    /// * `timeout` - Optional custom timeout for this request
+    /// * `no_retry_on` - Optional list of error kinds that should not trigger retry
+    /// * `context` - Optional context for error reporting
+    pub fn patch(
+        &self,
+        path: String,
+        params: Option<HashMap<String, String>>,
+        data: Option<String>,
+        headers: Option<HeaderMap>,
+        timeout: Option<u64>,
+        no_retry_on: Option<Vec<ErrorKind>>,
+        context: Option<Context>,
+    ) -> Result<String> {
+        self.do_request(
+            Method::PATCH,
+            path,
+            params,
+            data,
+            headers,
+            timeout,
+            no_retry_on,
+            context,
+        )
+    }
+
+    /// Makes a POST request with a body streamed from a file or a reader.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Request path relative to the root URL
+    /// * `params` - Optional query parameters
+    /// * `body` - Request body, see [`Body`]
+    /// * `headers` - Optional additional headers
+    /// * `timeout` - Optional custom timeout for this request
+    /// * `no_retry_on` - Optional list of error kinds that should not trigger retry
+    /// * `context` - Optional context for error reporting
+    pub fn post_body(
+        &self,
+        path: String,
+        params: Option<HashMap<String, String>>,
+        body: Body,
+        headers: Option<HeaderMap>,
+        timeout: Option<u64>,
+        no_retry_on: Option<Vec<ErrorKind>>,
+        context: Option<Context>,
+    ) -> Result<String> {
+        Ok(self
+            .execute(Method::POST, path, params, Some(body), headers, timeout, no_retry_on, context, None)?
+            .into_text())
+    }
+
+    /// Makes a PUT request with a body streamed from a file or a reader.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Request path relative to the root URL
+    /// * `params` - Optional query parameters
+    /// * `body` - Request body, see [`Body`]
+    /// * `headers` - Optional additional headers
+    /// * `timeout` - Optional custom timeout for this request
+    /// * `no_retry_on` - Optional list of error kinds that should not trigger retry
+    /// * `context` - Optional context for error reporting
+    pub fn put_body(
+        &self,
+        path: String,
+        params: Option<HashMap<String, String>>,
+        body: Body,
+        headers: Option<HeaderMap>,
+        timeout: Option<u64>,
+        no_retry_on: Option<Vec<ErrorKind>>,
+        context: Option<Context>,
+    ) -> Result<String> {
+        Ok(self
+            .execute(Method::PUT, path, params, Some(body), headers, timeout, no_retry_on, context, None)?
+            .into_text())
+    }
+
     /// Makes a DELETE request.
     ///
     /// # Arguments
@@ -688,15 +1049,76 @@ impl HttpClient {
             context,
         )
     }
+
+    /// Downloads `path` with a GET request, streaming the response body into `writer`.
+    ///
+    /// Returns the number of bytes written. Use [`request`](HttpClient::request) to set query
+    /// parameters, headers or a timeout.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Request path relative to the root URL
+    /// * `writer` - Destination of the response body
+    /// * `progress` - Optional callback receiving the bytes received so far and the expected total
+    pub fn download<W: Write + ?Sized>(
+        &self,
+        path: String,
+        writer: &mut W,
+        progress: Option<&mut dyn FnMut(u64, Option<u64>)>,
+    ) -> Result<u64> {
+        self.request(Method::GET, path).download(writer, progress)
+    }
+
+    /// Downloads `path` with a GET request, streaming the response body into `file`.
+    ///
+    /// The file is created or truncated; on failure, the partially written file is left in place.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Request path relative to the root URL
+    /// * `file` - Path of the destination file
+    /// * `progress` - Optional callback receiving the bytes received so far and the expected total
+    pub fn download_to_file<P: AsRef<Path>>(
+        &self,
+        path: String,
+        file: P,
+        progress: Option<&mut dyn FnMut(u64, Option<u64>)>,
+    ) -> Result<u64> {
+        self.request(Method::GET, path).download_to_file(file, progress)
+    }
+
+    /// Downloads `path` with a GET request into `file`, resuming the transfer with `Range` requests
+    /// after a network failure; see [`HttpRequest::download_resumable`].
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Request path relative to the root URL
+    /// * `file` - Path of the destination file
+    /// * `progress` - Optional callback receiving the bytes received so far and the expected total
+    pub fn download_resumable<P: AsRef<Path>>(
+        &self,
+        path: String,
+        file: P,
+        progress: Option<&mut dyn FnMut(u64, Option<u64>)>,
+    ) -> Result<u64> {
+        self.request(Method::GET, path).download_resumable(file, progress)
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use std::sync::Once;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Once};
+    use std::thread;
+    use std::time::{Duration, Instant};
 
     use simple_logger::SimpleLogger;
 
+    use crate::errors::client::NETWORK_CONNECTION;
     use crate::errors::http::UNPROCESSABLE_ENTITY;
+    use crate::retry::ConstantBackoff;
     use crate::{ClientBuilder, HttpClient};
 
     static INIT: Once = Once::new();
@@ -762,4 +1184,163 @@ mod test {
             }
         };
     }
+
+    /// Starts a keep-alive HTTP server answering `ok` and counting accepted connections.
+    fn serve_counting() -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let connections = Arc::new(AtomicUsize::new(0));
+        let counter = connections.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                counter.fetch_add(1, Ordering::SeqCst);
+                thread::spawn(move || {
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut stream = stream;
+                    loop {
+                        let mut line = String::new();
+                        loop {
+                            line.clear();
+                            match reader.read_line(&mut line) {
+                                Ok(0) | Err(_) => return,
+                                Ok(_) if line == "\r\n" => break,
+                                Ok(_) => {}
+                            }
+                        }
+                        let response = "HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok";
+                        if stream.write_all(response.as_bytes()).is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+        (format!("http://{}", addr), connections)
+    }
+
+    #[test]
+    fn test_pool_reuses_connection() {
+        init_logger();
+        let (url, connections) = serve_counting();
+        let cli = HttpClient::new(&url, None).unwrap();
+        for _ in 0..3 {
+            let result = cli.get("/".into(), None, None, None, None, None);
+            assert_eq!(result.unwrap(), "ok");
+        }
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_pool_disabled() {
+        init_logger();
+        let (url, connections) = serve_counting();
+        let cli = HttpClient::new(&url, None)
+            .unwrap()
+            .set_pool_max_idle_per_host(0);
+        for _ in 0..3 {
+            let result = cli.get("/".into(), None, None, None, None, None);
+            assert_eq!(result.unwrap(), "ok");
+        }
+        assert_eq!(connections.load(Ordering::SeqCst), 3);
+    }
+
+    /// Returns the url of a local port on which nothing listens.
+    fn closed_url() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    }
+
+    #[test]
+    fn test_network_error_retry() {
+        init_logger();
+        let cli = HttpClient::new(&closed_url(), None)
+            .unwrap()
+            .set_retry_number(3)
+            .set_retry_delay(0);
+        match cli.get("/".into(), None, None, None, None, None) {
+            Ok(_) => panic!("No error raised!"),
+            Err(err) => {
+                assert_eq!(err.kind, NETWORK_CONNECTION);
+                let details = err.details.expect("No error details found!");
+                assert_eq!(details.get("try"), Some(&serde_value::Value::U64(3)));
+            }
+        };
+    }
+
+    #[test]
+    fn test_network_error_no_retry() {
+        init_logger();
+        let cli = HttpClient::new(&closed_url(), None)
+            .unwrap()
+            .set_retry_number(3)
+            .set_retry_delay(0);
+        match cli.get("/".into(), None, None, None, Some(vec![NETWORK_CONNECTION]), None) {
+            Ok(_) => panic!("No error raised!"),
+            Err(err) => {
+                assert_eq!(err.kind, NETWORK_CONNECTION);
+                let details = err.details.expect("No error details found!");
+                assert_eq!(details.get("try"), Some(&serde_value::Value::U64(1)));
+            }
+        };
+    }
+
+    #[test]
+    fn test_network_error_recovered() {
+        init_logger();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            let mut incoming = listener.incoming().flatten();
+            // Drop the first connection without answering.
+            drop(incoming.next());
+            if let Some(mut stream) = incoming.next() {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap_or(0) > 0 && line != "\r\n" {
+                    line.clear();
+                }
+                let response = "HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok";
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        let cli = HttpClient::new(&url, None)
+            .unwrap()
+            .set_retry_number(2)
+            .set_retry_delay(0);
+        let result = cli.get("/".into(), None, None, None, None, None);
+        assert_eq!(result.unwrap(), "ok");
+    }
+
+    #[test]
+    fn test_retry_policy_budget() {
+        init_logger();
+        let policy = ConstantBackoff::new(Duration::from_millis(50))
+            .set_max_elapsed(Duration::from_millis(120));
+        let cli = HttpClient::new(&closed_url(), None)
+            .unwrap()
+            .set_retry_number(10)
+            .set_retry_policy(policy);
+        match cli.get("/".into(), None, None, None, None, None) {
+            Ok(_) => panic!("No error raised!"),
+            Err(err) => {
+                let details = err.details.expect("No error details found!");
+                match details.get("try") {
+                    Some(serde_value::Value::U64(req_try)) => assert!(*req_try < 10),
+                    _ => panic!("No try in error details"),
+                }
+            }
+        };
+    }
+
+    #[test]
+    fn test_no_delay_after_last_try() {
+        init_logger();
+        let cli = HttpClient::new(&closed_url(), None)
+            .unwrap()
+            .set_retry_number(1)
+            .set_retry_delay(30);
+        let start = Instant::now();
+        assert!(cli.get("/".into(), None, None, None, None, None).is_err());
+        assert!(start.elapsed() < Duration::from_secs(30));
+    }
 }
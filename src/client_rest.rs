@@ -8,11 +8,17 @@ REST-specific features.
 ## Features
 
 - Automatic JSON serialization/deserialization
+- URL-encoded form bodies, per request or as the client default
+- Pluggable codecs (XML, YAML, MessagePack, CBOR behind cargo features), with the decoder picked from the
+  response `Content-Type`
 - Type-safe request and response handling
 - Default JSON content type headers
 - Comprehensive error context for JSON parsing failures
-- Support for all standard REST methods (GET, POST, PUT, DELETE)
+- Support for all standard REST methods (GET, POST, PUT, PATCH, DELETE), with JSON Patch and JSON Merge
+  Patch documents
 - Generic type parameters for request bodies and responses
+- Empty responses decoded into `()` or `Option<R>`
+- Custom error bodies decoded and mapped onto the errors
 
 ## Examples
 
@@ -141,6 +147,35 @@ match client.get::<User>(
 }
 ```
 
+### Error Bodies
+
+`application/problem+json` error bodies are parsed into the error details, see
+[`problem`](crate::errors::problem). APIs with their own error envelope can be read with
+[`set_error_body`](RestClient::set_error_body):
+
+```rust,no_run
+use cdumay_error::Error;
+use cdumay_http_client::{ClientBuilder, RestClient};
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Deserialize)]
+struct ApiError {
+    code: String,
+    message: String,
+}
+
+let client = RestClient::new("https://api.example.com", None).unwrap()
+    .set_error_body(|body: ApiError, mut err: Error| {
+        err.message = format!("{}: {}", body.code, body.message);
+        err
+    });
+
+if let Err(err) = client.get::<Value>("/users/123".to_string(), None, None, None, None, None) {
+    println!("{}", err.message);
+}
+```
+
 ### Custom Headers
 
 ```rust
@@ -171,6 +206,76 @@ let result: Result<User> = client.get(
 );
 ```
 
+### Form Encoding
+
+Request bodies can be sent as `application/x-www-form-urlencoded` forms, either for a single request with
+the request builder, or for every `post` and `put` call with [`BodyEncoding::Form`]:
+
+```rust,no_run
+use cdumay_http_client::{BodyEncoding, ClientBuilder, RestClient};
+use reqwest::Method;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Token {
+    access_token: String,
+}
+
+let client = RestClient::new("https://auth.example.com", None).unwrap();
+
+let token: Token = client
+    .request(Method::POST, "/oauth/token")
+    .form(&[("grant_type", "client_credentials"), ("scope", "read")])
+    .send()
+    .unwrap();
+
+let legacy = RestClient::new("https://legacy.example.com", None).unwrap()
+    .set_body_encoding(BodyEncoding::Form);
+```
+
+### Empty Responses
+
+Responses without content (`204 No Content`, `205 Reset Content`, `304 Not Modified`, `HEAD` requests or
+empty bodies) are decoded as a unit value: request `()` when no content is expected, or an `Option` when
+the body may be empty.
+
+```rust,no_run
+use cdumay_http_client::{ClientBuilder, RestClient};
+use reqwest::header::{HeaderMap, HeaderValue, IF_NONE_MATCH};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct User {
+    id: u64,
+}
+
+let client = RestClient::new("https://api.example.com", None).unwrap();
+
+client.delete::<()>("/users/123".to_string(), None, None, None, None, None).unwrap();
+
+let mut headers = HeaderMap::new();
+headers.insert(IF_NONE_MATCH, HeaderValue::from_static("\"v42\""));
+let changed: Option<User> = client
+    .get("/users/456".to_string(), None, Some(headers), None, None, None)
+    .unwrap();
+```
+
+### Codecs
+
+Bodies are encoded and decoded by the client [`Codec`], JSON by default. Other codecs can be used instead
+or besides it: each response is decoded by the codec matching its `Content-Type` header; see
+[`codec`](crate::codec).
+
+```rust,ignore
+use cdumay_http_client::{ClientBuilder, RestClient};
+use cdumay_http_client::codec::{JsonCodec, XmlCodec};
+
+// Requires the `xml` feature: bodies are sent as XML, responses may be XML or JSON.
+let client = RestClient::new("https://api.example.com", None).unwrap()
+    .set_codec(XmlCodec::new())
+    .add_codec(JsonCodec);
+```
+
 ### Bulk Operations
 
 ```rust
@@ -206,20 +311,86 @@ let result: Result<Vec<Value>> = client.put(
 */
 
 use crate::authentication::Authentication;
+use crate::codec::{self, Codec, JsonCodec};
 use crate::errors::client::{InvalidHeaderValue, InvalidUrl};
-use crate::errors::rest::json_error_serialize;
+use crate::errors::http_resp_error;
+use crate::retry::{ConstantBackoff, RetryPolicy};
+use crate::request::HttpRequest;
+use crate::tls::TlsConfig;
+use crate::utils::get_or_try_init;
 use crate::{BaseClient, ClientBuilder};
 use cdumay_context::Context;
-use cdumay_error::{ErrorKind, Result};
+use cdumay_error::{Error, ErrorKind, Result};
+use reqwest::blocking::Client;
 use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, CONTENT_TYPE, USER_AGENT};
-use reqwest::{Method, Url};
+use reqwest::{Method, StatusCode, Url};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::ops::Deref;
+use std::sync::OnceLock;
+use std::time::Duration;
 use serde_value::Value;
 
+/// Encoding of the request bodies serialized by the REST clients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BodyEncoding {
+    /// The client [`Codec`], JSON by default.
+    #[default]
+    Codec,
+    /// `application/x-www-form-urlencoded`, expected by many legacy and OAuth endpoints.
+    Form,
+}
+
+impl BodyEncoding {
+    /// Returns the `Content-Type` header value of the encoding, given the client codec.
+    pub(crate) fn content_type(&self, codec: &dyn Codec) -> HeaderValue {
+        match self {
+            BodyEncoding::Codec => codec.content_type(),
+            BodyEncoding::Form => HeaderValue::from_static("application/x-www-form-urlencoded"),
+        }
+    }
+}
+
+/// Maps an error body, decoded by a codec, onto the error of the response.
+type ErrorBodyHook = dyn Fn(&dyn Codec, &[u8], Error) -> Error + Send + Sync;
+
+/// The custom error body registered on a REST client with `set_error_body`.
+pub(crate) struct ErrorBody(Box<ErrorBodyHook>);
+
+impl ErrorBody {
+    /// Decodes error bodies into `E` and passes them to `hook`; bodies which can't be decoded leave the
+    /// error unchanged.
+    pub(crate) fn new<E, F>(hook: F) -> ErrorBody
+    where
+        E: DeserializeOwned,
+        F: Fn(E, Error) -> Error + Send + Sync + 'static,
+    {
+        ErrorBody(Box::new(move |codec, body, error| {
+            match codec::decode::<E>(codec, body, Context::default()) {
+                Ok(value) => hook(value, error),
+                Err(_) => error,
+            }
+        }))
+    }
+
+    /// Applies the hook to the error built from an unsuccessful response.
+    pub(crate) fn apply(&self, codecs: &[Box<dyn Codec>], headers: &HeaderMap, body: &[u8], error: Error) -> Error {
+        if body.is_empty() {
+            return error;
+        }
+        let content_type = headers.get(CONTENT_TYPE).and_then(|value| value.to_str().ok());
+        (self.0)(codec::negotiate(codecs, content_type), body, error)
+    }
+}
+
+impl Debug for ErrorBody {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ErrorBody")
+    }
+}
+
 /// A specialized REST client that handles JSON serialization/deserialization.
 ///
 /// This client extends the base HTTP client functionality with REST-specific features:
@@ -257,8 +428,18 @@ pub struct RestClient {
     headers: HeaderMap,
     auth: Option<Box<dyn Authentication>>,
     ssl_verify: bool,
+    tls_config: TlsConfig,
     retry_number: u64,
-    retry_delay: u64,
+    retry_policy: Box<dyn RetryPolicy>,
+    max_retry_after: u64,
+    retry_non_idempotent: bool,
+    auto_idempotency_key: bool,
+    pool_idle_timeout: Option<u64>,
+    pool_max_idle_per_host: usize,
+    body_encoding: BodyEncoding,
+    codecs: Vec<Box<dyn Codec>>,
+    error_body: Option<ErrorBody>,
+    transport: OnceLock<Client>,
 }
 
 impl ClientBuilder for RestClient {
@@ -271,6 +452,7 @@ impl ClientBuilder for RestClient {
     /// - Retry attempts: 10
     /// - Retry delay: 30 seconds
     /// - SSL verification: enabled
+    /// - Idle pooled connections kept for 90 seconds
     ///
     /// # Arguments
     ///
@@ -313,8 +495,18 @@ impl ClientBuilder for RestClient {
             },
             auth: None,
             ssl_verify: true,
+            tls_config: TlsConfig::default(),
             retry_number: 10,
-            retry_delay: 30,
+            retry_policy: Box::new(ConstantBackoff::new(Duration::from_secs(30))),
+            max_retry_after: 120,
+            retry_non_idempotent: false,
+            auto_idempotency_key: false,
+            pool_idle_timeout: Some(90),
+            pool_max_idle_per_host: usize::MAX,
+            body_encoding: BodyEncoding::Codec,
+            codecs: vec![Box::new(JsonCodec)],
+            error_body: None,
+            transport: OnceLock::new(),
         })
     }
 
@@ -339,6 +531,14 @@ impl ClientBuilder for RestClient {
     /// Enables or disables SSL certificate verification.
     fn set_ssl_verify(mut self, ssl_verify: bool) -> RestClient {
         self.ssl_verify = ssl_verify;
+        self.transport = OnceLock::new();
+        self
+    }
+
+    /// Sets the TLS configuration used for all connections.
+    fn set_tls_config(mut self, tls_config: TlsConfig) -> RestClient {
+        self.tls_config = tls_config;
+        self.transport = OnceLock::new();
         self
     }
 
@@ -353,7 +553,41 @@ impl ClientBuilder for RestClient {
 
     /// Sets the delay between retry attempts in seconds.
     fn set_retry_delay(mut self, retry_delay: u64) -> RestClient {
-        self.retry_delay = retry_delay;
+        self.retry_policy = Box::new(ConstantBackoff::new(Duration::from_secs(retry_delay)));
+        self
+    }
+
+    fn set_retry_policy<P: RetryPolicy + 'static>(mut self, policy: P) -> RestClient {
+        self.retry_policy = Box::new(policy);
+        self
+    }
+
+    fn set_max_retry_after(mut self, max_retry_after: u64) -> RestClient {
+        self.max_retry_after = max_retry_after;
+        self
+    }
+
+    fn set_retry_non_idempotent(mut self, retry_non_idempotent: bool) -> RestClient {
+        self.retry_non_idempotent = retry_non_idempotent;
+        self
+    }
+
+    fn set_auto_idempotency_key(mut self, auto_idempotency_key: bool) -> RestClient {
+        self.auto_idempotency_key = auto_idempotency_key;
+        self
+    }
+
+    /// Sets how long, in seconds, idle pooled connections are kept alive.
+    fn set_pool_idle_timeout(mut self, idle_timeout: Option<u64>) -> RestClient {
+        self.pool_idle_timeout = idle_timeout;
+        self.transport = OnceLock::new();
+        self
+    }
+
+    /// Sets the maximum number of idle pooled connections per host.
+    fn set_pool_max_idle_per_host(mut self, max_idle: usize) -> RestClient {
+        self.pool_max_idle_per_host = max_idle;
+        self.transport = OnceLock::new();
         self
     }
 }
@@ -379,12 +613,48 @@ impl BaseClient for RestClient {
         self.ssl_verify
     }
 
+    fn tls_config(&self) -> &TlsConfig {
+        &self.tls_config
+    }
+
     fn retry_number(&self) -> u64 {
         self.retry_number
     }
 
-    fn retry_delay(&self) -> u64 {
-        self.retry_delay
+    fn retry_policy(&self) -> &dyn RetryPolicy {
+        self.retry_policy.as_ref()
+    }
+
+    fn max_retry_after(&self) -> u64 {
+        self.max_retry_after
+    }
+
+    fn retry_non_idempotent(&self) -> bool {
+        self.retry_non_idempotent
+    }
+
+    fn auto_idempotency_key(&self) -> bool {
+        self.auto_idempotency_key
+    }
+
+    fn pool_idle_timeout(&self) -> Option<u64> {
+        self.pool_idle_timeout
+    }
+
+    fn pool_max_idle_per_host(&self) -> usize {
+        self.pool_max_idle_per_host
+    }
+
+    fn transport(&self) -> Result<Client> {
+        get_or_try_init(&self.transport, || self.build_transport())
+    }
+
+    fn response_error(&self, status: StatusCode, headers: &HeaderMap, body: &[u8], context: Option<Context>) -> Error {
+        let error = http_resp_error(status, headers, body, context);
+        match &self.error_body {
+            Some(error_body) => error_body.apply(&self.codecs, headers, body, error),
+            None => error,
+        }
     }
 }
 
@@ -393,7 +663,7 @@ impl RestClient {
     ///
     /// This internal method is used to provide detailed context when errors occur,
     /// including the server URL, path, and HTTP method being used.
-    fn create_context(&self, path: String, method: Method) -> Context {
+    pub(crate) fn create_context(&self, path: String, method: Method) -> Context {
         let mut context = Context::default();
         context.insert(
             "server".into(),
@@ -407,6 +677,83 @@ impl RestClient {
         context
     }
 
+    /// Sets the encoding of the bodies sent by [`post`](RestClient::post) and [`put`](RestClient::put),
+    /// and the default `Content-Type` header accordingly.
+    ///
+    /// The request builder can still choose the encoding of each request, with
+    /// [`json`](HttpRequest::json) or [`form`](HttpRequest::form).
+    pub fn set_body_encoding(mut self, body_encoding: BodyEncoding) -> RestClient {
+        self.body_encoding = body_encoding;
+        self.headers.insert(CONTENT_TYPE, body_encoding.content_type(self.codec()));
+        self
+    }
+
+    /// Returns the encoding of the request bodies.
+    pub fn body_encoding(&self) -> BodyEncoding {
+        self.body_encoding
+    }
+
+    /// Sets the codec encoding the request bodies and decoding the responses, [`JsonCodec`] by default.
+    ///
+    /// The default `Content-Type` header follows the codec, unless bodies are sent as forms, and the
+    /// `Accept` header lists the media types of all the codecs.
+    pub fn set_codec<C: Codec + 'static>(mut self, codec: C) -> RestClient {
+        self.codecs[0] = Box::new(codec);
+        if self.body_encoding == BodyEncoding::Codec {
+            self.headers.insert(CONTENT_TYPE, self.codecs[0].content_type());
+        }
+        self.headers.insert(ACCEPT, codec::accept(&self.codecs));
+        self
+    }
+
+    /// Adds a codec decoding the responses of its media types, and adds them to the `Accept` header.
+    ///
+    /// Each response is decoded by the first codec accepting its `Content-Type`, or by the main codec.
+    pub fn add_codec<C: Codec + 'static>(mut self, codec: C) -> RestClient {
+        self.codecs.push(Box::new(codec));
+        self.headers.insert(ACCEPT, codec::accept(&self.codecs));
+        self
+    }
+
+    /// Returns the codec encoding the request bodies.
+    pub fn codec(&self) -> &dyn Codec {
+        self.codecs[0].as_ref()
+    }
+
+    /// Returns the codecs decoding the responses, starting with the main one.
+    pub(crate) fn codecs(&self) -> &[Box<dyn Codec>] {
+        &self.codecs
+    }
+
+    /// Sets how the error bodies of an API with its own error envelope are read.
+    ///
+    /// The body of each unsuccessful response is decoded into `E` by the client codecs and passed to `hook`
+    /// along with the error built from the response, which it returns updated. Bodies which can't be
+    /// decoded into `E` leave the error unchanged.
+    pub fn set_error_body<E, F>(mut self, hook: F) -> RestClient
+    where
+        E: DeserializeOwned,
+        F: Fn(E, Error) -> Error + Send + Sync + 'static,
+    {
+        self.error_body = Some(ErrorBody::new(hook));
+        self
+    }
+
+    /// Starts building a request to `path`, relative to the root URL.
+    ///
+    /// The response is decoded by the client codecs when the request is sent; see [`HttpRequest`].
+    pub fn request<P: Into<String>>(&self, method: Method, path: P) -> HttpRequest<'_, RestClient> {
+        HttpRequest::new(self, method, path)
+    }
+
+    /// Starts building a request using a method given by name, such as the WebDAV `PROPFIND` or `MKCOL`.
+    ///
+    /// An invalid method name is reported as a [`RequestError`](crate::errors::client::RequestError) when
+    /// the request is sent.
+    pub fn custom_request<P: Into<String>>(&self, method: &str, path: P) -> HttpRequest<'_, RestClient> {
+        HttpRequest::custom(self, method, path)
+    }
+
     /// Makes a GET request and deserializes the JSON response.
     ///
     /// # Type Parameters
@@ -439,17 +786,15 @@ impl RestClient {
     where
         R: DeserializeOwned,
     {
-        Ok(serde_json::from_str(&self.do_request(
-            Method::GET,
-            path.to_string(),
+        HttpRequest {
             params,
-            None,
             headers,
             timeout,
             no_retry_on,
-            context.clone(),
-        )?)
-        .map_err(|err| json_error_serialize(err, Some(context.unwrap_or(self.create_context(path, Method::GET)))))?)
+            context,
+            ..self.request(Method::GET, path)
+        }
+        .send()
     }
 
     /// Makes a POST request with an optional body and deserializes the JSON response.
@@ -488,24 +833,19 @@ impl RestClient {
         D: Serialize + Debug,
         R: DeserializeOwned,
     {
-        let payload = match data {
-            Some(txt) => Some(serde_json::to_string(&txt).map_err(|err| {
-                json_error_serialize(err, Some(context.clone().unwrap_or(self.create_context(path.clone(), Method::POST))))
-            })?),
-            None => None,
-        };
-        Ok(serde_json::from_str(&self.do_request(
-            Method::POST,
-            path.to_string(),
+        let request = HttpRequest {
             params,
-            payload,
             headers,
             timeout,
             no_retry_on,
-            context.clone(),
-        )?)
-        .map_err(|err| json_error_serialize(err, Some(
-            context.clone().unwrap_or(self.create_context(path, Method::POST)))))?)
+            context,
+            ..self.request(Method::POST, path)
+        };
+        match data {
+            Some(data) => request.serialize(&data),
+            None => request,
+        }
+        .send()
     }
 
     /// Makes a PUT request with an optional body and deserializes the JSON response.
@@ -544,23 +884,74 @@ impl RestClient {
         D: Serialize + Debug,
         R: DeserializeOwned,
     {
-        let payload = match data {
-            Some(txt) => Some(serde_json::to_string(&txt).map_err(|err| {
-                json_error_serialize(err, Some(context.clone().unwrap_or(self.create_context(path.clone(), Method::PUT))))
-            })?),
-            None => None,
+        let request = HttpRequest {
+            params,
+            headers,
+            timeout,
+            no_retry_on,
+            context,
+            ..self.request(Method::PUT, path)
         };
-        Ok(serde_json::from_str(&self.do_request(
-            Method::PUT,
-            path.to_string(),
+        match data {
+            Some(data) => request.serialize(&data),
+            None => request,
+        }
+        .send()
+    }
+
+    /// Makes a PATCH request with an optional body and deserializes the JSON response.
+    ///
+    /// The body is encoded by the client codec; use [`request`](RestClient::request) with
+    /// [`merge_patch`](HttpRequest::merge_patch) or [`json_patch`](HttpRequest::json_patch) to send a
+    /// JSON Merge Patch or a JSON Patch document.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `D` - The type of data to send in the request body
+    /// * `R` - The type to deserialize the response into
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Request path relative to the root URL
+    /// * `params` - Optional query parameters
+    /// * `data` - Optional request body to serialize as JSON
+    /// * `headers` - Optional additional headers
+    /// * `timeout` - Optional custom timeout for this request
+    /// * `no_retry_on` - Optional list of error kinds that should not trigger retry
+    /// * `context` - Optional context for error reporting
+    ///
+    /// # Returns
+    ///
+    /// Returns `Result<R>` which is:
+    /// - `Ok(R)` containing the deserialized response if successful
+    /// - `Err` with detailed error information if the request or deserialization fails
+    pub fn patch<D, R>(
+        &self,
+        path: String,
+        params: Option<HashMap<String, String>>,
+        data: Option<D>,
+        headers: Option<HeaderMap>,
+        timeout: Option<u64>,
+        no_retry_on: Option<Vec<ErrorKind>>,
+        context: Option<Context>,
+    ) -> Result<R>
+    where
+        D: Serialize + Debug,
+        R: DeserializeOwned,
+    {
+        let request = HttpRequest {
             params,
-            payload,
             headers,
             timeout,
             no_retry_on,
-            context.clone(),
-        )?)
-        .map_err(|err| json_error_serialize(err, Some(context.clone().unwrap_or(self.create_context(path, Method::PUT)))))?)
+            context,
+            ..self.request(Method::PATCH, path)
+        };
+        match data {
+            Some(data) => request.serialize(&data),
+            None => request,
+        }
+        .send()
     }
 
     /// Makes a DELETE request and deserializes the JSON response.
@@ -595,19 +986,15 @@ impl RestClient {
     where
         R: DeserializeOwned,
     {
-        Ok(serde_json::from_str(&self.do_request(
-            Method::DELETE,
-            path.to_string(),
+        HttpRequest {
             params,
-            None,
             headers,
             timeout,
             no_retry_on,
-            context.clone(),
-        )?)
-        .map_err(|err| {
-            json_error_serialize(err, Some(context.unwrap_or(self.create_context(path, Method::DELETE))))
-        })?)
+            context,
+            ..self.request(Method::DELETE, path)
+        }
+        .send()
     }
 }
 
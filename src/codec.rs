@@ -0,0 +1,434 @@
+/*!
+# Codec Module
+
+This module provides the [`Codec`] trait used by the REST clients to encode request bodies and decode
+responses, with the built-in [`JsonCodec`] used by default. Other formats are available behind cargo
+features:
+
+| Feature   | Codec           | Media type            |
+|-----------|-----------------|-----------------------|
+| `xml`     | `XmlCodec`      | `application/xml`     |
+| `yaml`    | `YamlCodec`     | `application/yaml`    |
+| `msgpack` | `MsgPackCodec`  | `application/msgpack` |
+| `cbor`    | `CborCodec`     | `application/cbor`    |
+
+A client encodes its requests with its main codec, set with `set_codec`, and picks the decoder of each
+response from its `Content-Type` header among all its codecs, registered with `add_codec`. Responses
+without a known media type are decoded by the main codec.
+
+The trait is object-safe: data goes through [`erased_serde`], so that custom codecs can be written for
+any serde format.
+
+## Examples
+
+```rust,no_run
+use cdumay_http_client::{ClientBuilder, RestClient};
+use cdumay_http_client::codec::JsonCodec;
+
+let client = RestClient::new("https://api.example.com", None).unwrap()
+    .set_codec(JsonCodec);
+```
+*/
+
+use cdumay_context::Context;
+use cdumay_error::{Error, Result};
+use mime::Mime;
+use reqwest::header::HeaderValue;
+use serde::de::value::UnitDeserializer;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::Debug;
+
+use crate::errors::rest::{json_error_serialize, EncodedDataError, JsonDataError};
+use crate::response::HttpResponse;
+
+/// Callback receiving the deserializer of a response body.
+pub type Visit<'a> = dyn FnMut(&mut dyn erased_serde::Deserializer<'_>) -> std::result::Result<(), erased_serde::Error> + 'a;
+
+/// Encodes request bodies and decodes response bodies in a given format.
+///
+/// The trait requires `Send + Sync` so that clients holding codecs can be shared between threads.
+pub trait Codec: Debug + Send + Sync {
+    /// Returns the media type of the encoded bodies, sent as `Content-Type` and `Accept` headers.
+    fn content_type(&self) -> HeaderValue;
+
+    /// Returns whether the codec decodes bodies of the given media type.
+    fn accepts(&self, mime: &Mime) -> bool;
+
+    /// Encodes `data`.
+    fn encode(&self, data: &dyn erased_serde::Serialize, context: Context) -> Result<Vec<u8>>;
+
+    /// Decodes `body` by handing a deserializer of its content to `visit`.
+    fn decode(&self, body: &[u8], visit: &mut Visit, context: Context) -> Result<()>;
+}
+
+/// Returns whether `mime` has the given subtype, or the given structured syntax suffix (`+json`...).
+fn matches(mime: &Mime, names: &[&str]) -> bool {
+    names
+        .iter()
+        .any(|name| mime.subtype() == *name || mime.suffix().map(|suffix| suffix == *name).unwrap_or(false))
+}
+
+/// Builds the error reported when a body does not match the requested type.
+fn data_error(err: impl ToString, context: Context) -> Error {
+    EncodedDataError::new()
+        .set_message(err.to_string())
+        .set_details(context.into())
+        .into()
+}
+
+/// Encodes `data` with `codec`.
+pub(crate) fn encode<D: Serialize + ?Sized>(codec: &dyn Codec, data: &D, context: Context) -> Result<Vec<u8>> {
+    // `&D` is sized even when `D` is not, so it can be erased.
+    codec.encode(&data, context)
+}
+
+/// Decodes `body` with `codec` into the requested type.
+pub(crate) fn decode<R: DeserializeOwned>(codec: &dyn Codec, body: &[u8], context: Context) -> Result<R> {
+    let mut value = None;
+    codec.decode(
+        body,
+        &mut |deserializer| {
+            value = Some(erased_serde::deserialize::<R>(deserializer)?);
+            Ok(())
+        },
+        context.clone(),
+    )?;
+    value.ok_or_else(|| data_error(format!("{:?} did not decode the body", codec), context))
+}
+
+/// Decodes `response` with the codec matching its `Content-Type`.
+///
+/// A response without content is read as a unit value, so that it can be decoded into `()` or `None`.
+pub(crate) fn decode_response<R: DeserializeOwned>(
+    codecs: &[Box<dyn Codec>],
+    response: &HttpResponse,
+    context: Context,
+) -> Result<R> {
+    if response.is_empty() {
+        return R::deserialize(UnitDeserializer::<serde::de::value::Error>::new())
+            .map_err(|err| data_error(format!("The response has no content: {}", err), context));
+    }
+    decode(negotiate(codecs, response.content_type()), response.bytes(), context)
+}
+
+/// Picks the codec decoding a body of the given `Content-Type`, the first one by default.
+pub(crate) fn negotiate<'a>(codecs: &'a [Box<dyn Codec>], content_type: Option<&str>) -> &'a dyn Codec {
+    let mime = content_type.and_then(|value| value.parse::<Mime>().ok());
+    mime.and_then(|mime| codecs.iter().find(|codec| codec.accepts(&mime)))
+        .unwrap_or(&codecs[0])
+        .as_ref()
+}
+
+/// Returns the `Accept` header listing the media types of `codecs`.
+pub(crate) fn accept(codecs: &[Box<dyn Codec>]) -> HeaderValue {
+    let types: Vec<HeaderValue> = codecs.iter().map(|codec| codec.content_type()).collect();
+    let value = types
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .collect::<Vec<&str>>()
+        .join(", ");
+    HeaderValue::from_str(&value).unwrap_or_else(|_| types[0].clone())
+}
+
+/// The JSON codec, used by default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonCodec;
+
+impl Codec for JsonCodec {
+    fn content_type(&self) -> HeaderValue {
+        HeaderValue::from_static("application/json")
+    }
+
+    /// Accepts `application/json` and the `+json` media types.
+    fn accepts(&self, mime: &Mime) -> bool {
+        matches(mime, &["json"])
+    }
+
+    fn encode(&self, data: &dyn erased_serde::Serialize, context: Context) -> Result<Vec<u8>> {
+        serde_json::to_vec(data).map_err(|err| json_error_serialize(err, Some(context)))
+    }
+
+    /// Parses the body first, so that syntax errors keep their own kind, then hands the parsed value.
+    fn decode(&self, body: &[u8], visit: &mut Visit, context: Context) -> Result<()> {
+        let value: serde_json::Value =
+            serde_json::from_slice(body).map_err(|err| json_error_serialize(err, Some(context.clone())))?;
+        visit(&mut <dyn erased_serde::Deserializer>::erase(value)).map_err(|err| {
+            JsonDataError::new()
+                .set_message(err.to_string())
+                .set_details(context.into())
+                .into()
+        })
+    }
+}
+
+/// The XML codec, available with the `xml` feature.
+///
+/// The root element of encoded bodies is named after the serialized type, unless set with
+/// [`set_root`](XmlCodec::set_root), which is required for maps.
+#[cfg(feature = "xml")]
+#[derive(Debug, Clone, Default)]
+pub struct XmlCodec {
+    root: Option<String>,
+}
+
+#[cfg(feature = "xml")]
+impl XmlCodec {
+    /// Creates an XML codec.
+    pub fn new() -> XmlCodec {
+        XmlCodec::default()
+    }
+
+    /// Sets the name of the root element of encoded bodies.
+    pub fn set_root<N: Into<String>>(mut self, root: N) -> XmlCodec {
+        self.root = Some(root.into());
+        self
+    }
+}
+
+#[cfg(feature = "xml")]
+impl Codec for XmlCodec {
+    fn content_type(&self) -> HeaderValue {
+        HeaderValue::from_static("application/xml")
+    }
+
+    /// Accepts `application/xml`, `text/xml` and the `+xml` media types.
+    fn accepts(&self, mime: &Mime) -> bool {
+        matches(mime, &["xml"])
+    }
+
+    fn encode(&self, data: &dyn erased_serde::Serialize, context: Context) -> Result<Vec<u8>> {
+        let encoded = match &self.root {
+            Some(root) => quick_xml::se::to_string_with_root(root, data),
+            None => quick_xml::se::to_string(data),
+        };
+        encoded
+            .map(String::into_bytes)
+            .map_err(|err| data_error(err, context))
+    }
+
+    fn decode(&self, body: &[u8], visit: &mut Visit, context: Context) -> Result<()> {
+        let text = std::str::from_utf8(body).map_err(|err| data_error(err, context.clone()))?;
+        let mut deserializer = quick_xml::de::Deserializer::from_str(text);
+        visit(&mut <dyn erased_serde::Deserializer>::erase(&mut deserializer)).map_err(|err| data_error(err, context))
+    }
+}
+
+/// The YAML codec, available with the `yaml` feature.
+#[cfg(feature = "yaml")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct YamlCodec;
+
+#[cfg(feature = "yaml")]
+impl Codec for YamlCodec {
+    fn content_type(&self) -> HeaderValue {
+        HeaderValue::from_static("application/yaml")
+    }
+
+    /// Accepts `application/yaml`, `application/x-yaml`, `text/yaml` and the `+yaml` media types.
+    fn accepts(&self, mime: &Mime) -> bool {
+        matches(mime, &["yaml", "x-yaml"])
+    }
+
+    fn encode(&self, data: &dyn erased_serde::Serialize, context: Context) -> Result<Vec<u8>> {
+        serde_yaml::to_string(data)
+            .map(String::into_bytes)
+            .map_err(|err| data_error(err, context))
+    }
+
+    fn decode(&self, body: &[u8], visit: &mut Visit, context: Context) -> Result<()> {
+        let deserializer = serde_yaml::Deserializer::from_slice(body);
+        visit(&mut <dyn erased_serde::Deserializer>::erase(deserializer)).map_err(|err| data_error(err, context))
+    }
+}
+
+/// The MessagePack codec, available with the `msgpack` feature.
+///
+/// Structs are encoded as maps, so that fields are matched by name.
+#[cfg(feature = "msgpack")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MsgPackCodec;
+
+#[cfg(feature = "msgpack")]
+impl Codec for MsgPackCodec {
+    fn content_type(&self) -> HeaderValue {
+        HeaderValue::from_static("application/msgpack")
+    }
+
+    /// Accepts `application/msgpack`, `application/x-msgpack` and `application/vnd.msgpack`.
+    fn accepts(&self, mime: &Mime) -> bool {
+        matches(mime, &["msgpack", "x-msgpack", "vnd.msgpack"])
+    }
+
+    fn encode(&self, data: &dyn erased_serde::Serialize, context: Context) -> Result<Vec<u8>> {
+        rmp_serde::to_vec_named(data).map_err(|err| data_error(err, context))
+    }
+
+    fn decode(&self, body: &[u8], visit: &mut Visit, context: Context) -> Result<()> {
+        let mut deserializer = rmp_serde::Deserializer::from_read_ref(body);
+        visit(&mut <dyn erased_serde::Deserializer>::erase(&mut deserializer)).map_err(|err| data_error(err, context))
+    }
+}
+
+/// The CBOR codec, available with the `cbor` feature.
+#[cfg(feature = "cbor")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CborCodec;
+
+#[cfg(feature = "cbor")]
+impl Codec for CborCodec {
+    fn content_type(&self) -> HeaderValue {
+        HeaderValue::from_static("application/cbor")
+    }
+
+    /// Accepts `application/cbor` and the `+cbor` media types.
+    fn accepts(&self, mime: &Mime) -> bool {
+        matches(mime, &["cbor"])
+    }
+
+    fn encode(&self, data: &dyn erased_serde::Serialize, context: Context) -> Result<Vec<u8>> {
+        let mut body = Vec::new();
+        ciborium::into_writer(data, &mut body).map_err(|err| data_error(err, context))?;
+        Ok(body)
+    }
+
+    /// ciborium does not expose its deserializer, so the body is first read as a self-describing value.
+    fn decode(&self, body: &[u8], visit: &mut Visit, context: Context) -> Result<()> {
+        let value: ciborium::Value = ciborium::from_reader(body).map_err(|err| data_error(err, context.clone()))?;
+        let value = serde_value::to_value(&value).map_err(|err| data_error(err, context.clone()))?;
+        visit(&mut <dyn erased_serde::Deserializer>::erase(value)).map_err(|err| data_error(err, context))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cdumay_context::Context;
+    use cdumay_error::ErrorKind;
+    use reqwest::header::HeaderValue;
+    use serde::{Deserialize, Serialize};
+    use wiremock::matchers::{header, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::{accept, decode, encode, negotiate, Codec, JsonCodec};
+    use crate::errors::rest::{DataError, SyntaxError};
+    use crate::{AsyncRestClient, ClientBuilder};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct User {
+        id: u64,
+        name: String,
+    }
+
+    fn round_trip(codec: &dyn Codec, kind: ErrorKind) {
+        let user = User { id: 1, name: "John".into() };
+        let body = encode(codec, &user, Context::default()).unwrap();
+        assert_eq!(decode::<User>(codec, &body, Context::default()).unwrap(), user);
+        let err = decode::<Vec<bool>>(codec, &body, Context::default()).unwrap_err();
+        assert_eq!(err.kind, kind);
+    }
+
+    #[test]
+    fn test_negotiate() {
+        let codecs: Vec<Box<dyn Codec>> = vec![Box::new(JsonCodec)];
+        assert!(negotiate(&codecs, Some("application/problem+json; charset=utf-8")).accepts(&mime::APPLICATION_JSON));
+        assert!(negotiate(&codecs, Some("text/csv")).accepts(&mime::APPLICATION_JSON));
+        assert!(negotiate(&codecs, None).accepts(&mime::APPLICATION_JSON));
+        assert_eq!(accept(&codecs), HeaderValue::from_static("application/json"));
+    }
+
+    #[test]
+    fn test_json_codec_errors() {
+        round_trip(&JsonCodec, DataError);
+        let err = decode::<User>(&JsonCodec, b"{\"id\": 1,", Context::default()).unwrap_err();
+        assert_eq!(err.kind, crate::errors::rest::EOF);
+        let err = decode::<User>(&JsonCodec, b"{id: 1}", Context::default()).unwrap_err();
+        assert_eq!(err.kind, SyntaxError);
+        let err = decode::<User>(&JsonCodec, b"{\"id\": \"1\"}", Context::default()).unwrap_err();
+        assert_eq!(err.kind, DataError);
+    }
+
+    #[cfg(feature = "xml")]
+    #[test]
+    fn test_xml_codec() {
+        round_trip(&super::XmlCodec::new(), crate::errors::rest::CodecDataError);
+        let body = encode(&super::XmlCodec::new(), &User { id: 1, name: "John".into() }, Context::default());
+        assert_eq!(body.unwrap(), b"<User><id>1</id><name>John</name></User>");
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_yaml_codec() {
+        round_trip(&super::YamlCodec, crate::errors::rest::CodecDataError);
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn test_msgpack_codec() {
+        round_trip(&super::MsgPackCodec, crate::errors::rest::CodecDataError);
+    }
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    fn test_cbor_codec() {
+        round_trip(&super::CborCodec, crate::errors::rest::CodecDataError);
+    }
+
+    #[tokio::test]
+    async fn test_content_negotiation() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(header("accept", "application/json"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "application/vnd.api+json")
+                    .set_body_string("{\"id\": 1, \"name\": \"John\"}"),
+            )
+            .mount(&server)
+            .await;
+        let cli = AsyncRestClient::new(&server.uri(), None).unwrap();
+        let user: User = cli.request(reqwest::Method::GET, "/users/1").send().await.unwrap();
+        assert_eq!(user, User { id: 1, name: "John".into() });
+    }
+
+    #[cfg(all(feature = "xml", feature = "yaml"))]
+    #[tokio::test]
+    async fn test_codec_client() {
+        use wiremock::matchers::{body_string, path};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/users"))
+            .and(header("content-type", "application/xml"))
+            .and(header("accept", "application/xml, application/json, application/yaml"))
+            .and(body_string("<User><id>1</id><name>John</name></User>"))
+            .respond_with(
+                ResponseTemplate::new(201)
+                    .insert_header("content-type", "application/xml")
+                    .set_body_string("<user><id>1</id><name>John</name></user>"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users/1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "application/yaml")
+                    .set_body_string("id: 1\nname: John\n"),
+            )
+            .mount(&server)
+            .await;
+        let cli = AsyncRestClient::new(&server.uri(), None)
+            .unwrap()
+            .set_codec(super::XmlCodec::new())
+            .add_codec(JsonCodec)
+            .add_codec(super::YamlCodec);
+        let user = User { id: 1, name: "John".into() };
+        let created: User = cli
+            .post("/users".into(), None, Some(&user), None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(created, user);
+        let fetched: User = cli.get("/users/1".into(), None, None, None, None, None).await.unwrap();
+        assert_eq!(fetched, user);
+    }
+}
@@ -0,0 +1,350 @@
+/*!
+# Download Module
+
+This module streams response bodies to a writer or a file, chunk by chunk, instead of buffering them in
+memory. Downloads are available on [`HttpClient`](crate::HttpClient), either directly or through the
+[request builder](crate::HttpRequest::download) to set query parameters, headers or a timeout.
+
+An optional callback is called after each chunk with the number of bytes received so far and the
+expected total, taken from the `Content-Length` header. When this header is present, the download fails
+with a [`NetworkError`](crate::errors::client::NetworkError) if the connection is closed before the
+announced length, or an [`InvalidContent`](crate::errors::client::InvalidContent) error if the body
+size differs once the transfer is complete. Failures to create or write the destination are reported as
+[`IoError`](crate::errors::client::IoError), with the URL in the error details.
+
+## Examples
+
+```rust,no_run
+use cdumay_http_client::{ClientBuilder, HttpClient};
+
+let client = HttpClient::new("https://downloads.example.com", None).unwrap();
+
+let size = client.download_to_file(
+    "/exports/users.csv".to_string(),
+    "/tmp/users.csv",
+    Some(&mut |received, total: Option<u64>| match total {
+        Some(total) => println!("{} / {} bytes", received, total),
+        None => println!("{} bytes", received),
+    }),
+).unwrap();
+```
+
+## Resumable Downloads
+
+[`HttpClient::download_resumable`](crate::HttpClient::download_resumable) resumes an interrupted transfer
+with a `Range` request instead of starting over, as long as the server sent an `ETag` or a
+`Last-Modified` date to check that the resource did not change. The number of attempts and the delay
+between them follow the client retry settings:
+
+```rust,no_run
+use cdumay_http_client::{ClientBuilder, HttpClient};
+
+let client = HttpClient::new("https://downloads.example.com", None).unwrap()
+    .set_retry_number(10)
+    .set_retry_delay(5);
+
+let size = client.download_resumable("/backups/nightly.tar.gz".to_string(), "/tmp/nightly.tar.gz", None).unwrap();
+```
+*/
+
+use cdumay_context::Context;
+use cdumay_error::Result;
+use reqwest::blocking::Response;
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_RANGE, ETAG, LAST_MODIFIED};
+use serde_value::Value;
+use std::io::{ErrorKind, Read, Write};
+
+use crate::errors::client::{InvalidContent, NetworkError};
+use crate::errors::io_error_serialize;
+
+/// Size of the chunks read from the response body.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Copies the body of `resp` into `writer`, returning `offset` plus the number of bytes written.
+///
+/// `offset` is the number of bytes already downloaded by a previous attempt, and `total` the expected
+/// size of the whole content. `progress` is called after each chunk with the bytes received so far,
+/// including `offset`, and `total`.
+pub(crate) fn copy_body<W: Write + ?Sized>(
+    mut resp: Response,
+    writer: &mut W,
+    mut progress: Option<&mut dyn FnMut(u64, Option<u64>)>,
+    offset: u64,
+    total: Option<u64>,
+    context: Context,
+) -> Result<u64> {
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+    let mut received = offset;
+    loop {
+        let read = match resp.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+            Err(err) => {
+                return Err(NetworkError::new()
+                    .set_message(err.to_string())
+                    .set_details(received_context(context, received, total).into())
+                    .into())
+            }
+        };
+        writer
+            .write_all(&buffer[..read])
+            .map_err(|err| io_error_serialize(&err, Some(received_context(context.clone(), received, total))))?;
+        received += read as u64;
+        if let Some(progress) = progress.as_mut() {
+            progress(received, total);
+        }
+    }
+    writer
+        .flush()
+        .map_err(|err| io_error_serialize(&err, Some(received_context(context.clone(), received, total))))?;
+    match total {
+        Some(total) if total != received => Err(InvalidContent::new()
+            .set_message(format!("Incomplete body: received {} bytes out of {}", received, total))
+            .set_details(received_context(context, received, Some(total)).into())
+            .into()),
+        _ => Ok(received),
+    }
+}
+
+/// Parses a `Content-Range: bytes <first>-<last>/<complete>` header into the position of its first byte
+/// and the complete length, if known.
+pub(crate) fn content_range(headers: &HeaderMap) -> Option<(u64, Option<u64>)> {
+    let value = headers.get(CONTENT_RANGE)?.to_str().ok()?;
+    let (range, complete) = value.strip_prefix("bytes ")?.split_once('/')?;
+    let (first, _) = range.split_once('-')?;
+    Some((first.trim().parse().ok()?, complete.trim().parse().ok()))
+}
+
+/// Returns the validator sent in the `If-Range` header to resume a download: the `ETag` if it is a strong
+/// one, the `Last-Modified` date otherwise.
+pub(crate) fn range_validator(headers: &HeaderMap) -> Option<HeaderValue> {
+    match headers.get(ETAG) {
+        Some(etag) if !etag.as_bytes().starts_with(b"W/") => Some(etag.clone()),
+        _ => headers.get(LAST_MODIFIED).cloned(),
+    }
+}
+
+/// Adds the number of bytes received, and expected if known, to the error context.
+fn received_context(mut context: Context, received: u64, expected: Option<u64>) -> Context {
+    context.insert("received".into(), Value::U64(received));
+    if let Some(expected) = expected {
+        context.insert("expected".into(), Value::U64(expected));
+    }
+    context
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    use reqwest::Method;
+    use serde_value::Value;
+
+    use crate::errors::client::{IO_ERROR, NETWORK_CONNECTION};
+    use crate::errors::http::{RANGE_NOT_SATISFIABLE, SERVICE_UNAVAILABLE};
+    use crate::{ClientBuilder, HttpClient};
+
+    /// Serves one connection per response, in order, and returns the request heads received.
+    fn serve(responses: Vec<(&'static str, &'static [u8])>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let received = requests.clone();
+        thread::spawn(move || {
+            for (head, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap_or(0) > 0 && line != "\r\n" {
+                    request.push_str(&line.to_lowercase());
+                    line.clear();
+                }
+                received.lock().unwrap().push(request);
+                let mut stream = stream;
+                let _ = stream.write_all(head.as_bytes());
+                let _ = stream.write_all(body);
+            }
+        });
+        (format!("http://{}", addr), requests)
+    }
+
+    fn serve_once(head: String, body: Vec<u8>) -> String {
+        let head: &'static str = Box::leak(head.into_boxed_str());
+        let body: &'static [u8] = Box::leak(body.into_boxed_slice());
+        serve(vec![(head, body)]).0
+    }
+
+    fn temp_file(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("cdumay_http_client_{}_{}", std::process::id(), name))
+    }
+
+    struct FailingWriter;
+
+    impl Write for FailingWriter {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("disk full"))
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_download() {
+        let body: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        let head = format!("HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n", body.len());
+        let url = serve_once(head, body.clone());
+        let cli = HttpClient::new(&url, None).unwrap();
+        let mut output = Vec::new();
+        let mut calls = Vec::new();
+        let size = cli
+            .download("/file".into(), &mut output, Some(&mut |received, total| calls.push((received, total))))
+            .unwrap();
+        assert_eq!(size, body.len() as u64);
+        assert_eq!(output, body);
+        assert!(calls.len() > 1);
+        assert!(calls.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert_eq!(calls.last(), Some(&(body.len() as u64, Some(body.len() as u64))));
+    }
+
+    #[test]
+    fn test_download_to_file() {
+        let url = serve_once("HTTP/1.1 200 OK\r\n\r\n".into(), b"id,name\n1,John\n".to_vec());
+        let file = temp_file("users.csv");
+        let cli = HttpClient::new(&url, None).unwrap();
+        let size = cli.download_to_file("/users.csv".into(), &file, None).unwrap();
+        assert_eq!(size, 15);
+        assert_eq!(std::fs::read(&file).unwrap(), b"id,name\n1,John\n");
+        std::fs::remove_file(&file).unwrap();
+    }
+
+    #[test]
+    fn test_download_incomplete() {
+        let url = serve_once("HTTP/1.1 200 OK\r\ncontent-length: 10\r\n\r\n".into(), b"12345".to_vec());
+        let cli = HttpClient::new(&url, None).unwrap();
+        let mut output = Vec::new();
+        let err = cli.download("/file".into(), &mut output, None).unwrap_err();
+        assert_eq!(err.kind, NETWORK_CONNECTION);
+        let details = err.details.unwrap();
+        assert!(details.contains_key("url"));
+        assert_eq!(details.get("received"), Some(&Value::U64(5)));
+        assert_eq!(details.get("expected"), Some(&Value::U64(10)));
+    }
+
+    #[test]
+    fn test_download_write_error() {
+        let url = serve_once("HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\n".into(), b"ok".to_vec());
+        let cli = HttpClient::new(&url, None).unwrap();
+        let err = cli.download("/file".into(), &mut FailingWriter, None).unwrap_err();
+        assert_eq!(err.kind, IO_ERROR);
+        assert_eq!(err.message, "disk full");
+    }
+
+    #[test]
+    fn test_download_resume() {
+        let (url, requests) = serve(vec![
+            ("HTTP/1.1 200 OK\r\ncontent-length: 10\r\netag: \"v1\"\r\n\r\n", b"01234"),
+            (
+                "HTTP/1.1 206 Partial Content\r\ncontent-length: 5\r\ncontent-range: bytes 5-9/10\r\n\r\n",
+                b"56789",
+            ),
+        ]);
+        let file = temp_file("resume.bin");
+        let cli = HttpClient::new(&url, None).unwrap().set_retry_number(3).set_retry_delay(0);
+        let mut last = None;
+        let size = cli
+            .download_resumable("/file".into(), &file, Some(&mut |received, total| last = Some((received, total))))
+            .unwrap();
+        assert_eq!(size, 10);
+        assert_eq!(last, Some((10, Some(10))));
+        assert_eq!(std::fs::read(&file).unwrap(), b"0123456789");
+        let requests = requests.lock().unwrap();
+        assert!(!requests[0].contains("range:"));
+        assert!(requests[1].contains("range: bytes=5-\r\n"));
+        assert!(requests[1].contains("if-range: \"v1\"\r\n"));
+        std::fs::remove_file(&file).unwrap();
+    }
+
+    #[test]
+    fn test_download_resume_restart() {
+        let (url, requests) = serve(vec![
+            ("HTTP/1.1 200 OK\r\ncontent-length: 10\r\nlast-modified: Wed, 21 Oct 2015 07:28:00 GMT\r\n\r\n", b"01234"),
+            ("HTTP/1.1 200 OK\r\ncontent-length: 4\r\n\r\n", b"abcd"),
+        ]);
+        let file = temp_file("restart.bin");
+        let cli = HttpClient::new(&url, None).unwrap().set_retry_number(3).set_retry_delay(0);
+        let size = cli.download_resumable("/file".into(), &file, None).unwrap();
+        assert_eq!(size, 4);
+        assert_eq!(std::fs::read(&file).unwrap(), b"abcd");
+        assert!(requests.lock().unwrap()[1].contains("if-range: wed, 21 oct 2015 07:28:00 gmt\r\n"));
+        std::fs::remove_file(&file).unwrap();
+    }
+
+    #[test]
+    fn test_download_resume_without_validator() {
+        let (url, requests) = serve(vec![
+            ("HTTP/1.1 200 OK\r\ncontent-length: 10\r\netag: W/\"v1\"\r\n\r\n", b"01234"),
+            ("HTTP/1.1 200 OK\r\ncontent-length: 10\r\n\r\n", b"0123456789"),
+        ]);
+        let file = temp_file("novalidator.bin");
+        let cli = HttpClient::new(&url, None).unwrap().set_retry_number(2).set_retry_delay(0);
+        assert_eq!(cli.download_resumable("/file".into(), &file, None).unwrap(), 10);
+        assert!(!requests.lock().unwrap()[1].contains("range:"));
+        std::fs::remove_file(&file).unwrap();
+    }
+
+    #[test]
+    fn test_download_resume_not_satisfiable() {
+        let (url, requests) = serve(vec![
+            ("HTTP/1.1 200 OK\r\ncontent-length: 10\r\netag: \"v1\"\r\n\r\n", b"01234"),
+            ("HTTP/1.1 416 Range Not Satisfiable\r\ncontent-length: 0\r\n\r\n", b""),
+        ]);
+        let file = temp_file("unsatisfiable.bin");
+        let cli = HttpClient::new(&url, None).unwrap().set_retry_number(3).set_retry_delay(0);
+        let err = cli.download_resumable("/file".into(), &file, None).unwrap_err();
+        assert_eq!(err.kind, RANGE_NOT_SATISFIABLE);
+        assert_eq!(requests.lock().unwrap().len(), 2);
+        std::fs::remove_file(&file).unwrap();
+    }
+
+    #[test]
+    fn test_download_resume_retry_number() {
+        let (url, requests) = serve(vec![
+            ("HTTP/1.1 503 Service Unavailable\r\ncontent-length: 0\r\n\r\n", b""),
+            ("HTTP/1.1 200 OK\r\ncontent-length: 10\r\netag: \"v1\"\r\n\r\n", b"01234"),
+            ("HTTP/1.1 503 Service Unavailable\r\ncontent-length: 0\r\n\r\n", b""),
+        ]);
+        let file = temp_file("retrynumber.bin");
+        let cli = HttpClient::new(&url, None).unwrap().set_retry_number(3).set_retry_delay(0);
+        let err = cli.download_resumable("/file".into(), &file, None).unwrap_err();
+        assert_eq!(err.kind, SERVICE_UNAVAILABLE);
+        assert_eq!(requests.lock().unwrap().len(), 3);
+        std::fs::remove_file(&file).unwrap();
+    }
+
+    #[test]
+    fn test_download_resume_request_retry_number() {
+        let (url, requests) = serve(vec![
+            ("HTTP/1.1 503 Service Unavailable\r\ncontent-length: 0\r\n\r\n", b""),
+            ("HTTP/1.1 200 OK\r\ncontent-length: 10\r\netag: \"v1\"\r\n\r\n", b"01234"),
+            ("HTTP/1.1 503 Service Unavailable\r\ncontent-length: 0\r\n\r\n", b""),
+        ]);
+        let file = temp_file("requestretrynumber.bin");
+        let cli = HttpClient::new(&url, None).unwrap().set_retry_number(5).set_retry_delay(0);
+        let err = cli
+            .request(Method::GET, "/file")
+            .retry_number(3)
+            .download_resumable(&file, None)
+            .unwrap_err();
+        assert_eq!(err.kind, SERVICE_UNAVAILABLE);
+        assert_eq!(requests.lock().unwrap().len(), 3);
+        std::fs::remove_file(&file).unwrap();
+    }
+}
@@ -7,6 +7,7 @@ define_kinds! {
     CONTENT_ERROR = ("Err-45973", 400, "The error is related to the request or response body"),
     NETWORK_CONNECTION = ("Err-64752", 500, "The error is related to connect"),
     REQUEST_ERROR = ("Err-37984", 500, "The error is related to the request"),
+    IO_ERROR = ("Err-83125", 500, "The error is related to a local read or write"),
 }
 
 define_errors! {
@@ -16,5 +17,6 @@ define_errors! {
     NetworkError = NETWORK_CONNECTION,
     RequestError = REQUEST_ERROR,
     UnexpectedError = UNKNOWN_ERROR,
-    InvalidHeaderValue = CONTENT_ERROR
+    InvalidHeaderValue = CONTENT_ERROR,
+    IoError = IO_ERROR
 }
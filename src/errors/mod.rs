@@ -1,17 +1,62 @@
 use cdumay_context::Context;
 use cdumay_error::Error;
 use reqwest::blocking::Response;
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use serde_value::Value;
+
+use crate::utils::retry_after;
+use problem::ProblemDetails;
 
 pub mod client;
 pub mod http;
+pub mod problem;
 pub mod rest;
 
+/// Adds the `Retry-After` delay of a response, rounded up to the second, to the error context.
+fn retry_after_context(status: StatusCode, headers: &HeaderMap, context: Option<Context>) -> Context {
+    let mut context = context.unwrap_or_default();
+    if let Some(delay) = retry_after(status, headers) {
+        let secs = delay.as_secs() + u64::from(delay.subsec_nanos() > 0);
+        context.insert("retry_after".into(), Value::U64(secs));
+    }
+    context
+}
+
+/// Builds the error of an unsuccessful response from its status, headers and body.
+///
+/// An `application/problem+json` body is parsed into the error details; see [`problem`].
+pub fn http_resp_error(status: StatusCode, headers: &HeaderMap, body: &[u8], context: Option<Context>) -> Error {
+    let mut context = retry_after_context(status, headers, context);
+    let text = String::from_utf8_lossy(body).into_owned();
+    let message = match ProblemDetails::from_response(headers, body) {
+        Some(problem) => {
+            let message = problem.message().map(str::to_string).unwrap_or(text);
+            if let Ok(value) = serde_value::to_value(&problem) {
+                context.insert(problem::PROBLEM_DETAILS.into(), value);
+            }
+            message
+        }
+        None => text,
+    };
+    http::from_status(status, message, context.into())
+}
+
 pub fn http_resp_serialise(resp: Response, context: Option<Context>) -> Error {
-    http::from_status(
-        resp.status(),
-        resp.text().unwrap_or_default(),
-        context.unwrap_or_default().into(),
-    )
+    let (status, headers) = (resp.status(), resp.headers().clone());
+    http_resp_error(status, &headers, &resp.bytes().unwrap_or_default(), context)
+}
+
+pub async fn http_async_resp_serialise(resp: reqwest::Response, context: Option<Context>) -> Error {
+    let (status, headers) = (resp.status(), resp.headers().clone());
+    http_resp_error(status, &headers, &resp.bytes().await.unwrap_or_default(), context)
+}
+
+pub fn io_error_serialize(error: &std::io::Error, context: Option<Context>) -> Error {
+    client::IoError::new()
+        .set_message(error.to_string())
+        .set_details(context.unwrap_or_default().into())
+        .into()
 }
 
 pub fn http_error_serialize(error: &reqwest::Error, context: Option<Context>) -> Error {
@@ -0,0 +1,151 @@
+//! Problem details (RFC 9457) returned by APIs in `application/problem+json` error responses.
+//!
+//! The clients parse these bodies when building the error of an unsuccessful response: the members are
+//! added to the error details under the `problem` key, and the `detail` (or `title`) member becomes the
+//! error message. [`ProblemDetails::from_error`] reads them back.
+
+use cdumay_error::Error;
+use mime::Mime;
+use reqwest::header::{HeaderMap, CONTENT_TYPE};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// Key of the problem details in the error details.
+pub const PROBLEM_DETAILS: &str = "problem";
+
+/// The members of a problem details object.
+///
+/// Standard members with an invalid type are ignored, as required by the RFC.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProblemDetails {
+    /// URI reference identifying the problem type, `about:blank` when absent.
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub problem_type: Option<String>,
+    /// Short summary of the problem type.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// HTTP status code generated by the origin server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// Explanation specific to this occurrence of the problem.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// URI reference identifying this occurrence of the problem.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// Extension members.
+    #[serde(flatten)]
+    pub extensions: BTreeMap<String, Value>,
+}
+
+impl ProblemDetails {
+    /// Parses the body of a response whose `Content-Type` is `application/problem+json`.
+    pub fn from_response(headers: &HeaderMap, body: &[u8]) -> Option<ProblemDetails> {
+        let mime = headers
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<Mime>().ok())?;
+        match mime.type_() == mime::APPLICATION
+            && mime.subtype() == "problem"
+            && mime.suffix() == Some(mime::JSON)
+        {
+            true => ProblemDetails::from_slice(body),
+            false => None,
+        }
+    }
+
+    /// Parses a problem details object.
+    pub fn from_slice(body: &[u8]) -> Option<ProblemDetails> {
+        let mut members: Map<String, Value> = serde_json::from_slice(body).ok()?;
+        let mut text = |name: &str| match members.remove(name) {
+            Some(Value::String(value)) => Some(value),
+            _ => None,
+        };
+        Some(ProblemDetails {
+            problem_type: text("type"),
+            title: text("title"),
+            detail: text("detail"),
+            instance: text("instance"),
+            status: members
+                .remove("status")
+                .and_then(|status| status.as_u64())
+                .and_then(|status| u16::try_from(status).ok()),
+            extensions: members.into_iter().collect(),
+        })
+    }
+
+    /// Reads the problem details added to the details of an error.
+    pub fn from_error(error: &Error) -> Option<ProblemDetails> {
+        let value = error.details.as_ref()?.get(PROBLEM_DETAILS)?;
+        value.clone().deserialize_into().ok()
+    }
+
+    /// Returns the message of the problem: its `detail` member, or its `title` member.
+    pub fn message(&self) -> Option<&str> {
+        self.detail.as_deref().or(self.title.as_deref())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+    use reqwest::StatusCode;
+    use serde_json::json;
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::ProblemDetails;
+    use crate::errors::http::{FORBIDDEN, NOT_FOUND};
+    use crate::errors::http_resp_error;
+    use crate::{AsyncHttpClient, ClientBuilder};
+
+    #[test]
+    fn test_problem_details() {
+        let body = br#"{"type": "https://example.com/probs/out-of-credit", "title": "You do not have enough credit.",
+            "status": "403", "instance": 42, "balance": 30, "accounts": ["/account/12345"]}"#;
+        let problem = ProblemDetails::from_slice(body).unwrap();
+        assert_eq!(problem.problem_type.as_deref(), Some("https://example.com/probs/out-of-credit"));
+        assert_eq!(problem.message(), Some("You do not have enough credit."));
+        assert_eq!(problem.status, None);
+        assert_eq!(problem.instance, None);
+        assert_eq!(problem.extensions["balance"], json!(30));
+        assert_eq!(problem.extensions["accounts"], json!(["/account/12345"]));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/problem+json; charset=utf-8"));
+        let err = http_resp_error(StatusCode::FORBIDDEN, &headers, body, None);
+        assert_eq!(err.kind, FORBIDDEN);
+        assert_eq!(err.message, "You do not have enough credit.");
+        assert_eq!(ProblemDetails::from_error(&err), Some(problem));
+
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        let err = http_resp_error(StatusCode::FORBIDDEN, &headers, body, None);
+        assert_eq!(ProblemDetails::from_error(&err), None);
+    }
+
+    #[tokio::test]
+    async fn test_problem_response() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(404).set_body_raw(
+                    json!({"title": "Not Found", "detail": "No user 42", "status": 404, "user": 42}).to_string(),
+                    "application/problem+json",
+                ),
+            )
+            .mount(&server)
+            .await;
+        let cli = AsyncHttpClient::new(&server.uri(), None).unwrap();
+        let err = cli
+            .get("/users/42".into(), None, None, None, Some(vec![NOT_FOUND]), None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind, NOT_FOUND);
+        assert_eq!(err.message, "No user 42");
+        let problem = ProblemDetails::from_error(&err).unwrap();
+        assert_eq!(problem.status, Some(404));
+        assert_eq!(problem.extensions["user"], json!(42));
+        assert!(err.details.unwrap().contains_key("url"));
+    }
+}
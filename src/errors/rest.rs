@@ -7,6 +7,8 @@ define_kinds! {
     SyntaxError = ("JSON-57633", 400, "Syntax Error"),
     DataError = ("JSON-15852", 400, "Invalid JSON data"),
     EOF = ("JSON-15853", 500, "Reached the end of the input data"),
+    FormDataError = ("FORM-40286", 400, "Invalid form data"),
+    CodecDataError = ("CODEC-71094", 400, "Invalid encoded data"),
 }
 
 define_errors! {
@@ -14,6 +16,8 @@ define_errors! {
     JsonSyntaxError = SyntaxError,
     JsonDataError = DataError,
     JsonEOF = EOF,
+    UrlEncodedDataError = FormDataError,
+    EncodedDataError = CodecDataError,
 }
 
 pub fn json_error_serialize(err: serde_json::Error, context: Option<Context>) -> Error {
@@ -37,3 +41,10 @@ pub fn json_error_serialize(err: serde_json::Error, context: Option<Context>) ->
             .into(),
     }
 }
+
+pub fn form_error_serialize(err: serde_urlencoded::ser::Error, context: Option<Context>) -> Error {
+    UrlEncodedDataError::new()
+        .set_message(err.to_string())
+        .set_details(context.unwrap_or_default().into())
+        .into()
+}
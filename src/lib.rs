@@ -9,12 +9,23 @@ A flexible and robust HTTP client library for Rust that provides both basic HTTP
 # Features
 
 - HTTP and REST client implementations
-- Configurable timeout, headers, and SSL verification
-- Authentication support
-- Automatic retry mechanism
-- Error handling with detailed context
-- JSON serialization/deserialization for REST client
+- Async counterparts of both clients for tokio applications
+- Configurable timeout, headers, SSL verification and TLS settings (custom CAs, minimum version)
+- Mutual TLS with client certificates (PEM or PKCS#12)
+- Authentication support (Basic, Digest, Bearer tokens, API keys, OAuth2 client credentials)
+- Automatic retry mechanism with pluggable backoff policies
+- Connection pooling with configurable idle timeout and size
+- Error handling with detailed context, and `application/problem+json` bodies parsed into the error details
+- JSON serialization/deserialization for REST client, with URL-encoded form bodies
+- Pluggable REST codecs, with optional XML, YAML, MessagePack and CBOR support and content negotiation
 - Query parameters support
+- Fluent request builder, with custom methods for WebDAV-style verbs
+- PATCH requests with JSON Patch and JSON Merge Patch documents, and OPTIONS capability discovery
+- Response objects exposing status, headers, final URL, attempt count and timing
+- Binary request and response bodies, with charset-aware text decoding
+- Streaming uploads from files and readers, replayed on retries
+- `multipart/form-data` forms with text, byte and file parts
+- Streaming downloads to a writer or a file, with progress reporting and resumption
 - Comprehensive logging
 
 # Basic Usage
@@ -98,6 +109,62 @@ fn main() {
 }
 ```
 
+## Async Clients
+
+`AsyncHttpClient` and `AsyncRestClient` expose the same configuration and methods as their blocking
+counterparts, but return futures and never block the executor between retries:
+
+```rust
+use cdumay_http_client::{AsyncRestClient, ClientBuilder};
+use serde::{Deserialize, Serialize};
+use cdumay_error::Result;
+
+#[derive(Serialize, Deserialize, Debug)]
+struct User {
+    id: i32,
+    name: String,
+}
+
+async fn get_user() -> Result<User> {
+    let client = AsyncRestClient::new("https://dummyjson.com", None)?
+        .set_timeout(30)
+        .set_retry_number(3);
+
+    client.get("/users/1".to_string(), None, None, None, None, None).await
+}
+```
+
+## Request Builder
+
+Every client also offers a fluent builder, which avoids the positional `None` arguments and only sets
+what differs from the client configuration:
+
+```rust,no_run
+use cdumay_http_client::{ClientBuilder, RestClient};
+use cdumay_http_client::errors::http::NOT_FOUND;
+use reqwest::Method;
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+struct User {
+    id: i32,
+    name: String,
+}
+
+let client = RestClient::new("https://dummyjson.com", None).unwrap();
+
+let users: Vec<User> = client
+    .request(Method::GET, "/users")
+    .query("limit", "10")
+    .timeout(5)
+    .no_retry_on(vec![NOT_FOUND])
+    .send()
+    .unwrap();
+```
+
+`execute` can be used instead of `send` to get an [`HttpResponse`] holding the status, the headers,
+the final URL, the number of attempts and the elapsed time along with the body.
+
 # Authentication
 
 The library supports custom authentication implementations through the `Authentication` trait:
@@ -151,7 +218,9 @@ let result: Result<String> = client.get(
 
 # Retry Mechanism
 
-Both clients support automatic retry with configurable attempts and delay:
+Both clients support automatic retry with configurable attempts and delay. Error responses as well
+as transport failures (connection refused, DNS failure, timeout) are retried, unless their kind is
+listed in `no_retry_on`:
 
 ```rust
 use cdumay_http_client::{HttpClient, ClientBuilder};
@@ -160,17 +229,72 @@ let client = HttpClient::new("https://dummyjson.com", None).unwrap()
     .set_retry_number(3)    // Maximum 3 retry attempts
     .set_retry_delay(1);    // 1 second delay between retries
 ```
+
+The delay between attempts is decided by a [`retry::RetryPolicy`]. Built-in policies provide constant,
+linear, exponential and decorrelated-jitter backoff with millisecond granularity, a maximum delay and a
+total elapsed time budget:
+
+```rust
+use std::time::Duration;
+use cdumay_http_client::{HttpClient, ClientBuilder};
+use cdumay_http_client::retry::DecorrelatedJitter;
+
+let client = HttpClient::new("https://dummyjson.com", None).unwrap()
+    .set_retry_number(5)
+    .set_retry_policy(
+        DecorrelatedJitter::new(Duration::from_millis(200))
+            .set_max_delay(Duration::from_secs(10))
+            .set_max_elapsed(Duration::from_secs(60)),
+    );
+```
+
+When a `429 Too Many Requests` or `503 Service Unavailable` response carries a `Retry-After` header
+(in seconds or as an HTTP-date), the next attempt waits the requested delay instead, bounded by
+`set_max_retry_after` (120 seconds by default). The delay is also exposed as `retry_after` (in
+seconds) in the error details, so that callers opting out of retries can schedule themselves.
+
+Requests using non-idempotent methods (`POST`, `PATCH`...) are sent only once, to avoid duplicated
+side effects. They are retried when the client enables `set_retry_non_idempotent`, or when the
+request carries an `Idempotency-Key` header, given by the caller or generated by the client with
+`set_auto_idempotency_key` (the same key is then sent with every attempt of a call):
+
+```rust
+use cdumay_http_client::{HttpClient, ClientBuilder};
+
+let client = HttpClient::new("https://dummyjson.com", None).unwrap()
+    .set_retry_number(3)
+    .set_auto_idempotency_key(true);
+```
+
+Both settings can be overridden for a single request with the `retry_non_idempotent` and
+`idempotency_key` methods of the request builder.
 */
 
 #[macro_use]
 extern crate log;
 
+pub use body::Body;
+pub use client_async_http::{AsyncBaseClient, AsyncHttpClient};
+pub use client_async_rest::AsyncRestClient;
 pub use client_http::{BaseClient, ClientBuilder, HttpClient};
-pub use client_rest::RestClient;
+pub use client_rest::{BodyEncoding, RestClient};
+pub use request::{HttpRequest, RequestOptions, RestLike};
+pub use response::HttpResponse;
 pub use utils::{build_url, merge_headers};
 
 pub mod authentication;
+mod body;
+mod client_async_http;
+mod client_async_rest;
 mod client_http;
 mod client_rest;
+pub mod codec;
+mod download;
 pub mod errors;
+pub mod multipart;
+pub mod patch;
+mod request;
+mod response;
+pub mod retry;
+pub mod tls;
 mod utils;
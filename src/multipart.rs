@@ -0,0 +1,327 @@
+/*!
+# Multipart Module
+
+This module provides [`Form`], a `multipart/form-data` body made of text fields, in-memory byte parts and
+file parts. A form is sent by the blocking clients through their request builder, with
+[`HttpRequest::multipart`](crate::HttpRequest::multipart), or as a [`Body`](crate::Body).
+
+The `Content-Type` header, holding the boundary, is set automatically and overrides the client default.
+Files are streamed instead of being loaded into memory, and are opened again when the request is
+retried: the form is rebuilt on each attempt.
+
+## Examples
+
+```rust,no_run
+use cdumay_http_client::{ClientBuilder, HttpClient};
+use cdumay_http_client::multipart::{Form, Part};
+use reqwest::Method;
+
+let client = HttpClient::new("https://api.example.com", None).unwrap();
+
+let form = Form::new()
+    .text("title", "Quarterly report")
+    .part("report", Part::file("/tmp/report.pdf").mime_str("application/pdf"))
+    .part("thumbnail", Part::bytes(vec![0x89, b'P', b'N', b'G']).file_name("thumbnail.png"));
+
+let result = client.request(Method::POST, "/documents").multipart(form).send();
+```
+*/
+
+use bytes::Bytes;
+use cdumay_context::Context;
+use cdumay_error::Result;
+use reqwest::header::HeaderValue;
+use serde_value::Value;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{Cursor, Read};
+use std::path::PathBuf;
+
+use crate::errors::io_error_serialize;
+use crate::utils::random_u64;
+
+/// A `multipart/form-data` form.
+#[derive(Debug, Clone)]
+pub struct Form {
+    boundary: String,
+    parts: Vec<(String, Part)>,
+}
+
+/// A part of a [`Form`].
+#[derive(Debug, Clone)]
+pub struct Part {
+    content: Content,
+    file_name: Option<String>,
+    mime: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+enum Content {
+    Bytes(Bytes),
+    File(PathBuf),
+}
+
+impl Default for Form {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Form {
+    /// Creates an empty form with a random boundary.
+    pub fn new() -> Form {
+        Form {
+            boundary: format!("{:016x}{:016x}", random_u64(), random_u64()),
+            parts: Vec::new(),
+        }
+    }
+
+    /// Returns the boundary separating the parts.
+    pub fn boundary(&self) -> &str {
+        &self.boundary
+    }
+
+    /// Adds a text field.
+    pub fn text<N: Into<String>, V: Into<String>>(self, name: N, value: V) -> Form {
+        self.part(name, Part::text(value))
+    }
+
+    /// Adds a file part, named after the file and sent as `application/octet-stream`.
+    pub fn file<N: Into<String>, P: Into<PathBuf>>(self, name: N, path: P) -> Form {
+        self.part(name, Part::file(path))
+    }
+
+    /// Adds a part.
+    pub fn part<N: Into<String>>(mut self, name: N, part: Part) -> Form {
+        self.parts.push((name.into(), part));
+        self
+    }
+
+    /// Returns the value of the `Content-Type` header of the form.
+    pub(crate) fn content_type(&self) -> HeaderValue {
+        // The boundary is made of hexadecimal digits, so the header value is always valid.
+        HeaderValue::from_str(&format!("multipart/form-data; boundary={}", self.boundary))
+            .unwrap_or_else(|_| HeaderValue::from_static("multipart/form-data"))
+    }
+
+    /// Opens the form for an attempt, returning a reader of the encoded body and its length.
+    pub(crate) fn open(&self, context: Context) -> Result<(FormReader, u64)> {
+        let mut readers: VecDeque<Box<dyn Read + Send>> = VecDeque::new();
+        let mut length = 0u64;
+        for (name, part) in &self.parts {
+            let head = part.head(&self.boundary, name);
+            length += head.len() as u64 + 2;
+            readers.push_back(Box::new(Cursor::new(head)));
+            match &part.content {
+                Content::Bytes(bytes) => {
+                    length += bytes.len() as u64;
+                    readers.push_back(Box::new(Cursor::new(bytes.clone())));
+                }
+                Content::File(path) => {
+                    let file = File::open(path)
+                        .and_then(|file| file.metadata().map(|metadata| (file, metadata.len())))
+                        .map_err(|err| {
+                            let mut context = context.clone();
+                            context.insert("file".into(), Value::String(path.display().to_string()));
+                            io_error_serialize(&err, Some(context))
+                        })?;
+                    length += file.1;
+                    readers.push_back(Box::new(file.0));
+                }
+            }
+            readers.push_back(Box::new(Cursor::new(b"\r\n")));
+        }
+        let tail = format!("--{}--\r\n", self.boundary).into_bytes();
+        length += tail.len() as u64;
+        readers.push_back(Box::new(Cursor::new(tail)));
+        Ok((FormReader { readers }, length))
+    }
+}
+
+impl Part {
+    /// Creates a text part.
+    pub fn text<V: Into<String>>(value: V) -> Part {
+        Part {
+            content: Content::Bytes(Bytes::from(value.into())),
+            file_name: None,
+            mime: None,
+        }
+    }
+
+    /// Creates a part from in-memory bytes.
+    pub fn bytes<B: Into<Bytes>>(bytes: B) -> Part {
+        Part {
+            content: Content::Bytes(bytes.into()),
+            file_name: None,
+            mime: None,
+        }
+    }
+
+    /// Creates a part streamed from the file at `path`, named after the file.
+    pub fn file<P: Into<PathBuf>>(path: P) -> Part {
+        let path = path.into();
+        Part {
+            file_name: path.file_name().map(|name| name.to_string_lossy().into_owned()),
+            content: Content::File(path),
+            mime: Some("application/octet-stream".into()),
+        }
+    }
+
+    /// Sets the file name of the part.
+    pub fn file_name<N: Into<String>>(mut self, file_name: N) -> Part {
+        self.file_name = Some(file_name.into());
+        self
+    }
+
+    /// Sets the content type of the part.
+    pub fn mime_str<M: Into<String>>(mut self, mime: M) -> Part {
+        self.mime = Some(mime.into());
+        self
+    }
+
+    /// Returns the boundary and the headers preceding the content of the part.
+    fn head(&self, boundary: &str, name: &str) -> Vec<u8> {
+        let mut head = format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"",
+            boundary,
+            escape(name)
+        );
+        if let Some(file_name) = &self.file_name {
+            head.push_str(&format!("; filename=\"{}\"", escape(file_name)));
+        }
+        head.push_str("\r\n");
+        if let Some(mime) = &self.mime {
+            head.push_str(&format!("Content-Type: {}\r\n", escape(mime)));
+        }
+        head.push_str("\r\n");
+        head.into_bytes()
+    }
+}
+
+/// Escapes the quotes and line breaks of a header parameter, as browsers do.
+fn escape(value: &str) -> String {
+    value
+        .replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Reads the encoded form, part after part.
+pub(crate) struct FormReader {
+    readers: VecDeque<Box<dyn Read + Send>>,
+}
+
+impl Read for FormReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while let Some(reader) = self.readers.front_mut() {
+            match reader.read(buf)? {
+                0 if !buf.is_empty() => {
+                    self.readers.pop_front();
+                }
+                read => return Ok(read),
+            }
+        }
+        Ok(0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Read;
+
+    use cdumay_context::Context;
+    use reqwest::Method;
+    use wiremock::matchers::{header_exists, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::{Form, Part};
+    use crate::{ClientBuilder, HttpClient, RestClient};
+
+    fn encode(form: &Form) -> (Vec<u8>, u64) {
+        let (mut reader, length) = form.open(Context::default()).unwrap();
+        let mut body = Vec::new();
+        reader.read_to_end(&mut body).unwrap();
+        (body, length)
+    }
+
+    #[test]
+    fn test_form_encoding() {
+        let form = Form {
+            boundary: "XYZ".into(),
+            ..Form::new()
+        }
+        .text("title", "Report")
+        .part("data", Part::bytes(vec![0u8, 1, 2]).file_name("a\"b.bin").mime_str("application/x-raw"));
+        let (body, length) = encode(&form);
+        let expected = b"--XYZ\r\nContent-Disposition: form-data; name=\"title\"\r\n\r\nReport\r\n\
+            --XYZ\r\nContent-Disposition: form-data; name=\"data\"; filename=\"a%22b.bin\"\r\n\
+            Content-Type: application/x-raw\r\n\r\n\x00\x01\x02\r\n--XYZ--\r\n";
+        assert_eq!(body, expected.to_vec());
+        assert_eq!(length, expected.len() as u64);
+        assert_eq!(form.content_type(), "multipart/form-data; boundary=XYZ");
+    }
+
+    #[tokio::test]
+    async fn test_multipart_retry() {
+        let file = std::env::temp_dir().join(format!("cdumay_http_client_{}_report.csv", std::process::id()));
+        std::fs::write(&file, "id,name\n1,John\n").unwrap();
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(header_exists("content-length"))
+            .respond_with(ResponseTemplate::new(201).set_body_string("created"))
+            .mount(&server)
+            .await;
+        let form = Form::new().text("kind", "users").file("report", &file);
+        let boundary = form.boundary().to_string();
+        let uri = server.uri();
+        let result = tokio::task::spawn_blocking(move || {
+            let cli = HttpClient::new(&uri, None)
+                .unwrap()
+                .set_retry_number(2)
+                .set_retry_delay(0)
+                .set_retry_non_idempotent(true);
+            cli.request(Method::POST, "/reports").multipart(form).send()
+        })
+        .await
+        .unwrap();
+        assert_eq!(result.unwrap(), "created");
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].body, requests[1].body);
+        assert_eq!(
+            requests[1].headers.get("content-type").unwrap(),
+            format!("multipart/form-data; boundary={}", boundary).as_str()
+        );
+        let body = String::from_utf8(requests[1].body.clone()).unwrap();
+        assert!(body.contains("name=\"report\"; filename=\"cdumay_http_client_"));
+        assert!(body.contains("Content-Type: application/octet-stream\r\n\r\nid,name\n1,John\n\r\n"));
+        std::fs::remove_file(&file).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_multipart_rest() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"id": 1})))
+            .mount(&server)
+            .await;
+        let uri = server.uri();
+        let result = tokio::task::spawn_blocking(move || {
+            let cli = RestClient::new(&uri, None).unwrap();
+            cli.request(Method::POST, "/avatars")
+                .multipart(Form::new().part("avatar", Part::bytes(vec![1u8, 2, 3]).mime_str("image/png")))
+                .send::<serde_json::Value>()
+        })
+        .await
+        .unwrap();
+        assert_eq!(result.unwrap(), serde_json::json!({"id": 1}));
+        let requests = server.received_requests().await.unwrap();
+        let content_type = requests[0].headers.get("content-type").unwrap().to_str().unwrap();
+        assert!(content_type.starts_with("multipart/form-data; boundary="));
+    }
+}
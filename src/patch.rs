@@ -0,0 +1,161 @@
+/*!
+# Patch Module
+
+This module provides [`JsonPatch`], a builder of [JSON Patch (RFC 6902)](https://www.rfc-editor.org/rfc/rfc6902)
+documents: ordered lists of operations sent as `application/json-patch+json` with
+[`HttpRequest::json_patch`](crate::HttpRequest::json_patch).
+
+Partial updates can also be sent as [JSON Merge Patch (RFC 7396)](https://www.rfc-editor.org/rfc/rfc7396)
+documents with [`HttpRequest::merge_patch`](crate::HttpRequest::merge_patch), or encoded by the client
+codec with the `patch` method of the REST clients.
+
+## Examples
+
+```rust,no_run
+use cdumay_http_client::{ClientBuilder, RestClient};
+use cdumay_http_client::patch::JsonPatch;
+use reqwest::Method;
+use serde_json::{json, Value};
+
+let client = RestClient::new("https://api.example.com", None).unwrap();
+
+let patch = JsonPatch::new()
+    .test("/version", 3)
+    .replace("/email", "john@example.com")
+    .add("/roles/-", "admin")
+    .remove("/nickname");
+
+let user: Value = client
+    .request(Method::PATCH, "/users/123")
+    .json_patch(&patch)
+    .send()
+    .unwrap();
+
+let user: Value = client
+    .request(Method::PATCH, "/users/123")
+    .merge_patch(&json!({"email": "john@example.com", "nickname": null}))
+    .send()
+    .unwrap();
+```
+*/
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A JSON Patch document, serialized as the array of its operations.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct JsonPatch {
+    operations: Vec<Operation>,
+}
+
+/// A JSON Patch operation; paths are JSON Pointers (RFC 6901).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum Operation {
+    /// Adds `value` at `path`, inserting it into arrays.
+    Add { path: String, value: Value },
+    /// Removes the value at `path`.
+    Remove { path: String },
+    /// Replaces the value at `path` with `value`.
+    Replace { path: String, value: Value },
+    /// Moves the value at `from` to `path`.
+    Move { from: String, path: String },
+    /// Copies the value at `from` to `path`.
+    Copy { from: String, path: String },
+    /// Checks that the value at `path` equals `value`, failing the whole patch otherwise.
+    Test { path: String, value: Value },
+}
+
+impl JsonPatch {
+    /// Creates an empty patch.
+    pub fn new() -> JsonPatch {
+        JsonPatch::default()
+    }
+
+    /// Returns the operations of the patch.
+    pub fn operations(&self) -> &[Operation] {
+        &self.operations
+    }
+
+    /// Adds an operation.
+    pub fn push(mut self, operation: Operation) -> JsonPatch {
+        self.operations.push(operation);
+        self
+    }
+
+    /// Adds an `add` operation.
+    pub fn add<P: Into<String>, V: Into<Value>>(self, path: P, value: V) -> JsonPatch {
+        self.push(Operation::Add {
+            path: path.into(),
+            value: value.into(),
+        })
+    }
+
+    /// Adds a `remove` operation.
+    pub fn remove<P: Into<String>>(self, path: P) -> JsonPatch {
+        self.push(Operation::Remove { path: path.into() })
+    }
+
+    /// Adds a `replace` operation.
+    pub fn replace<P: Into<String>, V: Into<Value>>(self, path: P, value: V) -> JsonPatch {
+        self.push(Operation::Replace {
+            path: path.into(),
+            value: value.into(),
+        })
+    }
+
+    /// Adds a `move` operation.
+    pub fn move_to<F: Into<String>, P: Into<String>>(self, from: F, path: P) -> JsonPatch {
+        self.push(Operation::Move {
+            from: from.into(),
+            path: path.into(),
+        })
+    }
+
+    /// Adds a `copy` operation.
+    pub fn copy<F: Into<String>, P: Into<String>>(self, from: F, path: P) -> JsonPatch {
+        self.push(Operation::Copy {
+            from: from.into(),
+            path: path.into(),
+        })
+    }
+
+    /// Adds a `test` operation.
+    pub fn test<P: Into<String>, V: Into<Value>>(self, path: P, value: V) -> JsonPatch {
+        self.push(Operation::Test {
+            path: path.into(),
+            value: value.into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::JsonPatch;
+
+    #[test]
+    fn test_json_patch() {
+        let patch = JsonPatch::new()
+            .test("/version", 3)
+            .add("/roles/-", "admin")
+            .remove("/nickname")
+            .replace("/name", json!({"first": "John"}))
+            .move_to("/old", "/new")
+            .copy("/a", "/b");
+        assert_eq!(
+            serde_json::to_value(&patch).unwrap(),
+            json!([
+                {"op": "test", "path": "/version", "value": 3},
+                {"op": "add", "path": "/roles/-", "value": "admin"},
+                {"op": "remove", "path": "/nickname"},
+                {"op": "replace", "path": "/name", "value": {"first": "John"}},
+                {"op": "move", "from": "/old", "path": "/new"},
+                {"op": "copy", "from": "/a", "path": "/b"}
+            ])
+        );
+        assert_eq!(serde_json::from_value::<JsonPatch>(serde_json::to_value(&patch).unwrap()).unwrap(), patch);
+    }
+}
@@ -0,0 +1,976 @@
+/*!
+# Request Builder Module
+
+This module provides [`HttpRequest`], a fluent builder created by the `request` method of every client.
+It gathers the optional settings of a request (query parameters, headers, body, timeout, retry
+exclusions and error context) and sends it through the client's `do_request`, replacing long lists
+of positional `None` arguments.
+
+## Examples
+
+### HTTP Client
+
+```rust,no_run
+use cdumay_http_client::{ClientBuilder, HttpClient};
+use cdumay_http_client::errors::http::NOT_FOUND;
+use reqwest::header::{HeaderValue, ACCEPT};
+use reqwest::Method;
+
+let client = HttpClient::new("https://api.example.com", None).unwrap();
+
+let result = client
+    .request(Method::GET, "/users")
+    .query("role", "admin")
+    .header(ACCEPT, HeaderValue::from_static("text/csv"))
+    .timeout(5)
+    .no_retry_on(vec![NOT_FOUND])
+    .send();
+```
+
+### REST Client
+
+With a [`RestClient`](crate::RestClient), the body is serialized from any `Serialize` value and the
+response is deserialized into the requested type:
+
+```rust,no_run
+use cdumay_http_client::{ClientBuilder, RestClient};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+struct CreateUser {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct User {
+    id: u64,
+    name: String,
+}
+
+let client = RestClient::new("https://api.example.com", None).unwrap();
+
+let user: User = client
+    .request(Method::POST, "/users")
+    .json(&CreateUser { name: "John Doe".into() })
+    .send()
+    .unwrap();
+```
+*/
+
+use bytes::Bytes;
+use cdumay_context::Context;
+use cdumay_error::{Error, ErrorKind, Result};
+use chrono::Utc;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE, IF_RANGE, RANGE};
+use reqwest::{Method, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_value::Value;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Seek, SeekFrom, Write};
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+use crate::body::Body;
+use crate::codec::{self, Codec};
+use crate::download::{content_range, copy_body, range_validator};
+use crate::errors::client::{InvalidContent, InvalidHeaderValue, RequestError, NETWORK_CONNECTION};
+use crate::errors::http::RANGE_NOT_SATISFIABLE;
+use crate::errors::io_error_serialize;
+use crate::errors::rest::{form_error_serialize, json_error_serialize};
+use crate::multipart::Form;
+use crate::patch::JsonPatch;
+use crate::response::HttpResponse;
+use crate::retry::RetryState;
+use crate::utils::strip_auth_query;
+use crate::{AsyncBaseClient, AsyncHttpClient, AsyncRestClient, BaseClient, BodyEncoding, HttpClient, RestClient};
+
+/// Settings of a request overriding the client configuration.
+///
+/// They are set with the methods of [`HttpRequest`], or passed to the `execute_raw` and `execute`
+/// methods of the clients; the settings left unset fall back to the client configuration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RequestOptions {
+    /// Whether the request is retried even though its method is not idempotent; see
+    /// [`ClientBuilder::set_retry_non_idempotent`](crate::ClientBuilder::set_retry_non_idempotent).
+    pub retry_non_idempotent: Option<bool>,
+    /// Whether an `Idempotency-Key` header is generated for a `POST` or `PATCH` request; see
+    /// [`ClientBuilder::set_auto_idempotency_key`](crate::ClientBuilder::set_auto_idempotency_key).
+    pub idempotency_key: Option<bool>,
+    /// Maximum number of times the request is sent, set with [`HttpRequest::retry_number`] so that it is
+    /// never zero; see [`ClientBuilder::set_retry_number`](crate::ClientBuilder::set_retry_number).
+    pub(crate) retry_number: Option<u64>,
+}
+
+/// A request being built for a client.
+///
+/// Created by [`HttpClient::request`], [`RestClient::request`], [`AsyncHttpClient::request`] and
+/// [`AsyncRestClient::request`]; the settings left unset fall back to the client configuration.
+#[derive(Debug)]
+pub struct HttpRequest<'a, C> {
+    pub(crate) client: &'a C,
+    pub(crate) method: Method,
+    pub(crate) path: String,
+    pub(crate) params: Option<HashMap<String, String>>,
+    pub(crate) data: Option<Body>,
+    pub(crate) headers: Option<HeaderMap>,
+    pub(crate) timeout: Option<u64>,
+    pub(crate) no_retry_on: Option<Vec<ErrorKind>>,
+    pub(crate) context: Option<Context>,
+    pub(crate) options: RequestOptions,
+    pub(crate) error: Option<Error>,
+}
+
+impl<'a, C> HttpRequest<'a, C> {
+    /// Creates a request for `path`, relative to the client root URL.
+    pub(crate) fn new<P: Into<String>>(client: &'a C, method: Method, path: P) -> HttpRequest<'a, C> {
+        HttpRequest {
+            client,
+            method,
+            path: path.into(),
+            params: None,
+            data: None,
+            headers: None,
+            timeout: None,
+            no_retry_on: None,
+            context: None,
+            options: RequestOptions::default(),
+            error: None,
+        }
+    }
+
+    /// Creates a request using a method given by name, such as the WebDAV `PROPFIND`.
+    ///
+    /// An invalid method name is reported when the request is sent.
+    pub(crate) fn custom<P: Into<String>>(client: &'a C, method: &str, path: P) -> HttpRequest<'a, C> {
+        match Method::from_bytes(method.as_bytes()) {
+            Ok(method) => HttpRequest::new(client, method, path),
+            Err(err) => {
+                let mut request = HttpRequest::new(client, Method::GET, path);
+                let mut context = Context::default();
+                context.insert("method".into(), Value::String(method.to_string()));
+                request.error = Some(
+                    RequestError::new()
+                        .set_message(format!("Invalid HTTP method: {}", err))
+                        .set_details(context.into())
+                        .into(),
+                );
+                request
+            }
+        }
+    }
+
+    /// Adds a query parameter.
+    pub fn query<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.params
+            .get_or_insert_with(HashMap::new)
+            .insert(key.into(), value.into());
+        self
+    }
+
+    /// Adds several query parameters.
+    pub fn params(mut self, params: HashMap<String, String>) -> Self {
+        self.params.get_or_insert_with(HashMap::new).extend(params);
+        self
+    }
+
+    /// Adds a header, overriding the client header with the same name.
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers
+            .get_or_insert_with(HeaderMap::new)
+            .insert(name, value);
+        self
+    }
+
+    /// Adds several headers, overriding the client headers with the same names.
+    pub fn headers(mut self, headers: HeaderMap) -> Self {
+        self.headers.get_or_insert_with(HeaderMap::new).extend(headers);
+        self
+    }
+
+    /// Sets the raw request body, either text or binary data.
+    pub fn body<B: Into<Bytes>>(mut self, body: B) -> Self {
+        self.data = Some(Body::from(body.into()));
+        self
+    }
+
+    /// Sets the timeout of this request in seconds.
+    pub fn timeout(mut self, timeout: u64) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the error kinds which must not trigger a retry.
+    pub fn no_retry_on(mut self, no_retry_on: Vec<ErrorKind>) -> Self {
+        self.no_retry_on = Some(no_retry_on);
+        self
+    }
+
+    /// Sets the context added to the error details.
+    pub fn context(mut self, context: Context) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    /// Enables or disables the retries of this request even though its method is not idempotent,
+    /// overriding [`ClientBuilder::set_retry_non_idempotent`](crate::ClientBuilder::set_retry_non_idempotent).
+    pub fn retry_non_idempotent(mut self, retry_non_idempotent: bool) -> Self {
+        self.options.retry_non_idempotent = Some(retry_non_idempotent);
+        self
+    }
+
+    /// Enables or disables the generation of an `Idempotency-Key` header for this request, overriding
+    /// [`ClientBuilder::set_auto_idempotency_key`](crate::ClientBuilder::set_auto_idempotency_key).
+    ///
+    /// Only `POST` and `PATCH` requests get a generated key; a key given in the request headers is
+    /// kept as is.
+    pub fn idempotency_key(mut self, idempotency_key: bool) -> Self {
+        self.options.idempotency_key = Some(idempotency_key);
+        self
+    }
+
+    /// Sets the maximum number of times this request is sent, overriding
+    /// [`ClientBuilder::set_retry_number`](crate::ClientBuilder::set_retry_number).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `retry_number` is zero.
+    pub fn retry_number(mut self, retry_number: u64) -> Self {
+        if retry_number == 0 {
+            panic!("Try number MUST be > 0 !");
+        }
+        self.options.retry_number = Some(retry_number);
+        self
+    }
+
+    /// Returns the error raised while building the request, if any.
+    fn check(&mut self) -> Result<()> {
+        match self.error.take() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Takes the in-memory body, the only kind sent by async clients.
+    fn take_bytes(&mut self) -> Result<Option<Bytes>> {
+        match self.data.take() {
+            Some(body) => Ok(Some(body.into_bytes(self.context.clone().unwrap_or_default())?)),
+            None => Ok(None),
+        }
+    }
+}
+
+impl HttpRequest<'_, HttpClient> {
+    /// Sets the request body, which may be streamed from a file or a reader; see [`Body`].
+    pub fn upload(mut self, body: Body) -> Self {
+        self.data = Some(body);
+        self
+    }
+
+    /// Sets the request body to a `multipart/form-data` form; see [`Form`].
+    pub fn multipart(mut self, form: Form) -> Self {
+        self.data = Some(Body::from(form));
+        self
+    }
+
+    /// Sends the request and returns the response body as text.
+    pub fn send(self) -> Result<String> {
+        Ok(self.execute()?.into_text())
+    }
+
+    /// Sends the request and returns the raw response body.
+    pub fn send_bytes(self) -> Result<Bytes> {
+        Ok(self.execute()?.into_bytes())
+    }
+
+    /// Sends the request and returns the whole response.
+    pub fn execute(mut self) -> Result<HttpResponse> {
+        self.check()?;
+        self.client.execute(
+            self.method,
+            self.path,
+            self.params,
+            self.data,
+            self.headers,
+            self.timeout,
+            self.no_retry_on,
+            self.context,
+            Some(self.options),
+        )
+    }
+
+    /// Sends the request and streams the response body into `writer`, returning the number of bytes
+    /// written.
+    ///
+    /// `progress` is called after each chunk with the number of bytes received so far and the
+    /// expected total, if the server sent a `Content-Length` header.
+    pub fn download<W: Write + ?Sized>(
+        mut self,
+        writer: &mut W,
+        progress: Option<&mut dyn FnMut(u64, Option<u64>)>,
+    ) -> Result<u64> {
+        self.check()?;
+        let mut context = self.context.clone().unwrap_or_default();
+        let (resp, _) = self.client.execute_raw(
+            self.method.clone(),
+            self.path,
+            self.params,
+            self.data.as_ref(),
+            self.headers,
+            self.timeout,
+            self.no_retry_on,
+            self.context,
+            Some(self.options),
+        )?;
+        let url = strip_auth_query(resp.url(), self.client.auth().map(|auth| auth.as_ref()));
+        context.insert("url".into(), Value::String(url.to_string()));
+        context.insert("method".into(), Value::String(self.method.to_string()));
+        let total = resp.content_length();
+        copy_body(resp, writer, progress, 0, total, context)
+    }
+
+    /// Sends the request and streams the response body into `file`, which is created or truncated.
+    ///
+    /// On failure, the partially written file is left in place.
+    pub fn download_to_file<P: AsRef<Path>>(
+        mut self,
+        file: P,
+        progress: Option<&mut dyn FnMut(u64, Option<u64>)>,
+    ) -> Result<u64> {
+        self.check()?;
+        let mut output =
+            File::create(file.as_ref()).map_err(|err| io_error_serialize(&err, Some(self.file_context(file.as_ref()))))?;
+        self.download(&mut output, progress)
+    }
+
+    /// Sends the request and streams the response body into `file`, resuming the transfer after a
+    /// network failure instead of starting over.
+    ///
+    /// When the body is interrupted, the request is sent again, up to the client retry number and
+    /// following its retry policy, with a `Range: bytes=<received>-` header asking for the missing
+    /// part and an `If-Range` header holding the `ETag` (or `Last-Modified` date) of the previous
+    /// response. If the server answers `200 OK` instead of `206 Partial Content`, because it ignores
+    /// ranges or the resource changed in the meantime, the file is truncated and the download restarts
+    /// from zero; without a strong validator, it always restarts. A `416 Range Not Satisfiable` answer
+    /// is returned as a [`RangeNotSatisfiable`](crate::errors::http::RangeNotSatisfiable) error.
+    ///
+    /// The retry number of the request, or of the client when unset, bounds the number of requests sent
+    /// in total, whether they retry a failed request or resume an interrupted body.
+    ///
+    /// Returns the size of the downloaded file.
+    pub fn download_resumable<P: AsRef<Path>>(
+        mut self,
+        file: P,
+        mut progress: Option<&mut dyn FnMut(u64, Option<u64>)>,
+    ) -> Result<u64> {
+        self.check()?;
+        let start = Utc::now();
+        let mut context = self.file_context(file.as_ref());
+        let mut output = File::create(file.as_ref()).map_err(|err| io_error_serialize(&err, Some(context.clone())))?;
+        let mut no_retry_on = self.no_retry_on.clone().unwrap_or_default();
+        no_retry_on.push(RANGE_NOT_SATISFIABLE);
+        let mut received = 0u64;
+        let mut validator: Option<HeaderValue> = None;
+        let mut last_delay: Option<Duration> = None;
+        let retry_number = self.options.retry_number.unwrap_or(self.client.retry_number());
+        let mut attempts = 0;
+        loop {
+            let mut headers = self.headers.clone().unwrap_or_default();
+            if let Some(validator) = validator.as_ref().filter(|_| received > 0) {
+                let range = HeaderValue::from_str(&format!("bytes={}-", received))
+                    .map_err(|err| InvalidHeaderValue::new().set_message(err.to_string()))?;
+                headers.insert(RANGE, range);
+                headers.insert(IF_RANGE, validator.clone());
+            }
+            let options = RequestOptions {
+                retry_number: Some(retry_number - attempts),
+                ..self.options
+            };
+            let (resp, tries) = self.client.execute_raw(
+                self.method.clone(),
+                self.path.clone(),
+                self.params.clone(),
+                self.data.as_ref(),
+                Some(headers),
+                self.timeout,
+                Some(no_retry_on.clone()),
+                Some(context.clone()),
+                Some(options),
+            )?;
+            attempts += tries;
+            let url = strip_auth_query(resp.url(), self.client.auth().map(|auth| auth.as_ref()));
+            context.insert("url".into(), Value::String(url.to_string()));
+            let total = match (resp.status(), content_range(resp.headers())) {
+                (StatusCode::PARTIAL_CONTENT, Some((first, total))) if first == received => {
+                    total.or_else(|| resp.content_length().map(|length| received + length))
+                }
+                (StatusCode::PARTIAL_CONTENT, _) => {
+                    return Err(InvalidContent::new()
+                        .set_message(format!("Unexpected partial content, expected a range starting at {}", received))
+                        .set_details(context.into())
+                        .into())
+                }
+                _ => {
+                    if received > 0 {
+                        debug!("[{}] - {} (range ignored, restarting download)", self.method, url);
+                        output
+                            .set_len(0)
+                            .and_then(|_| output.seek(SeekFrom::Start(0)))
+                            .map_err(|err| io_error_serialize(&err, Some(context.clone())))?;
+                        received = 0;
+                    }
+                    validator = range_validator(resp.headers());
+                    resp.content_length()
+                }
+            };
+            let progress = progress.as_mut().map(|progress| &mut **progress as &mut dyn FnMut(u64, Option<u64>));
+            let err = match copy_body(resp, &mut output, progress, received, total, context.clone()) {
+                Ok(size) => return Ok(size),
+                Err(err) if err.kind == NETWORK_CONNECTION => err,
+                Err(err) => return Err(err),
+            };
+            received = output
+                .stream_position()
+                .map_err(|err| io_error_serialize(&err, Some(context.clone())))?;
+            if attempts >= retry_number {
+                return Err(err);
+            }
+            let state = RetryState {
+                attempt: attempts,
+                elapsed: { Utc::now() - start }.to_std().unwrap_or_default(),
+                last_delay,
+            };
+            match self.client.retry_policy().next_delay(&state) {
+                Some(delay) => {
+                    debug!(
+                        "[{}] - {} (resuming at {} in {})",
+                        self.method,
+                        self.path,
+                        received,
+                        humantime::format_duration(delay)
+                    );
+                    thread::sleep(delay);
+                    last_delay = Some(delay);
+                }
+                None => return Err(err),
+            }
+        }
+    }
+
+    /// Returns the context reported by errors related to the destination file.
+    fn file_context(&self, file: &Path) -> Context {
+        let mut context = self.context.clone().unwrap_or_default();
+        context.insert("path".into(), Value::String(self.path.clone()));
+        context.insert("method".into(), Value::String(self.method.to_string()));
+        context.insert("file".into(), Value::String(file.display().to_string()));
+        context
+    }
+}
+
+impl HttpRequest<'_, AsyncHttpClient> {
+    /// Sends the request and returns the response body as text.
+    pub async fn send(self) -> Result<String> {
+        Ok(self.execute().await?.into_text())
+    }
+
+    /// Sends the request and returns the raw response body.
+    pub async fn send_bytes(self) -> Result<Bytes> {
+        Ok(self.execute().await?.into_bytes())
+    }
+
+    /// Sends the request and returns the whole response.
+    pub async fn execute(mut self) -> Result<HttpResponse> {
+        self.check()?;
+        let data = self.take_bytes()?;
+        self.client
+            .execute(
+                self.method,
+                self.path,
+                self.params,
+                data,
+                self.headers,
+                self.timeout,
+                self.no_retry_on,
+                self.context,
+                Some(self.options),
+            )
+            .await
+    }
+}
+
+mod sealed {
+    /// Prevents [`RestLike`](super::RestLike) from being implemented outside this crate.
+    pub trait Sealed {}
+
+    impl Sealed for crate::RestClient {}
+    impl Sealed for crate::AsyncRestClient {}
+}
+
+/// The settings of the REST clients used to build the body of their requests.
+///
+/// Implemented by [`RestClient`] and [`AsyncRestClient`], whose requests get the body methods of
+/// [`HttpRequest`], such as [`json`](HttpRequest::json) and [`form`](HttpRequest::form), from it. This
+/// trait is sealed: it cannot be implemented outside this crate.
+pub trait RestLike: sealed::Sealed {
+    /// Returns the codec encoding the request bodies.
+    fn codec(&self) -> &dyn Codec;
+
+    /// Returns how the request bodies are serialized.
+    fn body_encoding(&self) -> BodyEncoding;
+
+    /// Returns the headers sent with every request.
+    fn default_headers(&self) -> &HeaderMap;
+
+    /// Returns the context reported by encoding and decoding errors.
+    fn create_context(&self, path: String, method: Method) -> Context;
+}
+
+impl RestLike for RestClient {
+    fn codec(&self) -> &dyn Codec {
+        RestClient::codec(self)
+    }
+
+    fn body_encoding(&self) -> BodyEncoding {
+        RestClient::body_encoding(self)
+    }
+
+    fn default_headers(&self) -> &HeaderMap {
+        BaseClient::headers(self)
+    }
+
+    fn create_context(&self, path: String, method: Method) -> Context {
+        RestClient::create_context(self, path, method)
+    }
+}
+
+impl RestLike for AsyncRestClient {
+    fn codec(&self) -> &dyn Codec {
+        AsyncRestClient::codec(self)
+    }
+
+    fn body_encoding(&self) -> BodyEncoding {
+        AsyncRestClient::body_encoding(self)
+    }
+
+    fn default_headers(&self) -> &HeaderMap {
+        AsyncBaseClient::headers(self)
+    }
+
+    fn create_context(&self, path: String, method: Method) -> Context {
+        AsyncRestClient::create_context(self, path, method)
+    }
+}
+
+impl<C: RestLike> HttpRequest<'_, C> {
+    /// Sets the request body, serialized as JSON whatever the client codec.
+    pub fn json<D: Serialize + ?Sized>(self, data: &D) -> Self {
+        self.json_body(data, "application/json")
+    }
+
+    /// Sets the request body to a JSON Merge Patch (RFC 7396) document, sent as
+    /// `application/merge-patch+json`: `null` members are removed from the target.
+    pub fn merge_patch<D: Serialize + ?Sized>(self, data: &D) -> Self {
+        self.json_body(data, "application/merge-patch+json")
+    }
+
+    /// Sets the request body to a JSON Patch (RFC 6902) document, sent as `application/json-patch+json`.
+    pub fn json_patch(self, patch: &JsonPatch) -> Self {
+        self.json_body(patch, "application/json-patch+json")
+    }
+
+    /// Sets the request body, serialized as JSON and sent with the given `Content-Type`.
+    fn json_body<D: Serialize + ?Sized>(mut self, data: &D, content_type: &'static str) -> Self {
+        match serde_json::to_vec(data) {
+            Ok(payload) => {
+                self.data = Some(Body::from(payload));
+                self.set_content_type(HeaderValue::from_static(content_type));
+            }
+            Err(err) => {
+                let context = self.error_context();
+                self.error = Some(json_error_serialize(err, Some(context)));
+            }
+        }
+        self
+    }
+
+    /// Sets the request body, serialized as a URL-encoded form.
+    pub fn form<D: Serialize + ?Sized>(mut self, data: &D) -> Self {
+        match serde_urlencoded::to_string(data) {
+            Ok(payload) => {
+                self.data = Some(Body::from(payload));
+                self.set_content_type(HeaderValue::from_static("application/x-www-form-urlencoded"));
+            }
+            Err(err) => {
+                let context = self.error_context();
+                self.error = Some(form_error_serialize(err, Some(context)));
+            }
+        }
+        self
+    }
+
+    /// Sets the request body, encoded with the client [`Codec`](crate::codec::Codec).
+    pub fn encode<D: Serialize + ?Sized>(mut self, data: &D) -> Self {
+        let codec = self.client.codec();
+        match codec::encode(codec, data, self.error_context()) {
+            Ok(payload) => {
+                self.data = Some(Body::from(payload));
+                self.set_content_type(codec.content_type());
+            }
+            Err(err) => self.error = Some(err),
+        }
+        self
+    }
+
+    /// Sets the request body, serialized with the client [`BodyEncoding`].
+    pub(crate) fn serialize<D: Serialize + ?Sized>(self, data: &D) -> Self {
+        match self.client.body_encoding() {
+            BodyEncoding::Codec => self.encode(data),
+            BodyEncoding::Form => self.form(data),
+        }
+    }
+
+    /// Sets the `Content-Type` header, unless it is the client default or the header was already set for
+    /// this request.
+    fn set_content_type(&mut self, content_type: HeaderValue) {
+        if self.client.default_headers().get(CONTENT_TYPE) != Some(&content_type) {
+            self.headers
+                .get_or_insert_with(HeaderMap::new)
+                .entry(CONTENT_TYPE)
+                .or_insert(content_type);
+        }
+    }
+
+    /// Returns the context reported by encoding and decoding errors.
+    fn error_context(&self) -> Context {
+        self.context
+            .clone()
+            .unwrap_or_else(|| self.client.create_context(self.path.clone(), self.method.clone()))
+    }
+}
+
+impl HttpRequest<'_, RestClient> {
+    /// Sets the request body to a `multipart/form-data` form; see [`Form`].
+    pub fn multipart(mut self, form: Form) -> Self {
+        self.data = Some(Body::from(form));
+        self
+    }
+
+    /// Sends the request and decodes the response with the client codec matching its `Content-Type`.
+    ///
+    /// A response without content decodes into `()` or `None`, so `R` can be an `Option` when the body may
+    /// be empty.
+    pub fn send<R: DeserializeOwned>(self) -> Result<R> {
+        let (client, context) = (self.client, self.error_context());
+        let response = self.execute()?;
+        codec::decode_response(client.codecs(), &response, context)
+    }
+
+    /// Sends the request and returns the whole response, whose body can be decoded with
+    /// [`HttpResponse::json`].
+    pub fn execute(mut self) -> Result<HttpResponse> {
+        self.check()?;
+        self.client.execute(
+            self.method,
+            self.path,
+            self.params,
+            self.data,
+            self.headers,
+            self.timeout,
+            self.no_retry_on,
+            self.context,
+            Some(self.options),
+        )
+    }
+}
+
+impl HttpRequest<'_, AsyncRestClient> {
+    /// Sends the request and decodes the response with the client codec matching its `Content-Type`.
+    ///
+    /// A response without content decodes into `()` or `None`, so `R` can be an `Option` when the body may
+    /// be empty.
+    pub async fn send<R: DeserializeOwned>(self) -> Result<R> {
+        let (client, context) = (self.client, self.error_context());
+        let response = self.execute().await?;
+        codec::decode_response(client.codecs(), &response, context)
+    }
+
+    /// Sends the request and returns the whole response, whose body can be decoded with
+    /// [`HttpResponse::json`].
+    pub async fn execute(mut self) -> Result<HttpResponse> {
+        self.check()?;
+        let data = self.take_bytes()?;
+        self.client
+            .execute(
+                self.method,
+                self.path,
+                self.params,
+                data,
+                self.headers,
+                self.timeout,
+                self.no_retry_on,
+                self.context,
+                Some(self.options),
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use reqwest::header::{HeaderValue, ACCEPT};
+    use reqwest::Method;
+    use serde::{Deserialize, Serialize};
+    use wiremock::matchers::{body_json, body_string, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use crate::errors::http::{INTERNAL_SERVER_ERROR, NOT_FOUND};
+    use crate::errors::rest::{CodecDataError, FormDataError, SyntaxError};
+    use crate::patch::JsonPatch;
+    use crate::{AsyncHttpClient, AsyncRestClient, BodyEncoding, ClientBuilder, RestClient};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct User {
+        id: u64,
+        name: String,
+    }
+
+    #[tokio::test]
+    async fn test_http_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users"))
+            .and(query_param("role", "admin"))
+            .and(header("accept", "text/csv"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("id,name"))
+            .mount(&server)
+            .await;
+        let cli = AsyncHttpClient::new(&server.uri(), None).unwrap();
+        let result = cli
+            .request(Method::GET, "/users")
+            .query("role", "admin")
+            .header(ACCEPT, HeaderValue::from_static("text/csv"))
+            .timeout(5)
+            .send()
+            .await;
+        assert_eq!(result.unwrap(), "id,name");
+    }
+
+    #[tokio::test]
+    async fn test_http_request_no_retry_on() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        let cli = AsyncHttpClient::new(&server.uri(), None).unwrap();
+        let err = cli
+            .request(Method::GET, "/missing")
+            .no_retry_on(vec![NOT_FOUND])
+            .send()
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind, NOT_FOUND);
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_http_request_retry_number() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+        let cli = AsyncHttpClient::new(&server.uri(), None)
+            .unwrap()
+            .set_retry_number(1)
+            .set_retry_delay(0);
+        let err = cli.request(Method::GET, "/").retry_number(3).send().await.unwrap_err();
+        assert_eq!(err.kind, INTERNAL_SERVER_ERROR);
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Try number MUST be > 0 !")]
+    fn test_http_request_retry_number_zero() {
+        let cli = AsyncHttpClient::new("http://localhost", None).unwrap();
+        let _ = cli.request(Method::GET, "/").retry_number(0);
+    }
+
+    #[tokio::test]
+    async fn test_rest_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/users"))
+            .and(body_json(serde_json::json!({"id": 0, "name": "John"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"id": 1, "name": "John"})))
+            .mount(&server)
+            .await;
+        let uri = server.uri();
+        let user = tokio::task::spawn_blocking(move || {
+            let cli = RestClient::new(&uri, None).unwrap();
+            cli.request(Method::POST, "/users")
+                .json(&User { id: 0, name: "John".into() })
+                .send::<User>()
+        })
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(user, User { id: 1, name: "John".into() });
+    }
+
+    #[tokio::test]
+    async fn test_rest_request_form() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .and(header("content-type", "application/x-www-form-urlencoded"))
+            .and(body_string("grant_type=client_credentials&scope=read+write"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"id": 1, "name": "token"})))
+            .mount(&server)
+            .await;
+        let cli = AsyncRestClient::new(&server.uri(), None).unwrap();
+        let user = cli
+            .request(Method::POST, "/token")
+            .form(&[("grant_type", "client_credentials"), ("scope", "read write")])
+            .send::<User>()
+            .await
+            .unwrap();
+        assert_eq!(user.name, "token");
+    }
+
+    #[tokio::test]
+    async fn test_rest_client_form_encoding() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(header("content-type", "application/x-www-form-urlencoded"))
+            .and(body_string("id=0&name=John+Doe"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"id": 1, "name": "form"})))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(header("content-type", "application/json"))
+            .and(body_json(serde_json::json!({"id": 0, "name": "John Doe"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"id": 2, "name": "json"})))
+            .mount(&server)
+            .await;
+        let cli = AsyncRestClient::new(&server.uri(), None)
+            .unwrap()
+            .set_body_encoding(BodyEncoding::Form);
+        let data = User { id: 0, name: "John Doe".into() };
+        let user: User = cli
+            .post("/users".into(), None, Some(&data), None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(user.name, "form");
+        let user: User = cli.request(Method::POST, "/users").json(&data).send().await.unwrap();
+        assert_eq!(user.name, "json");
+    }
+
+    #[tokio::test]
+    async fn test_rest_request_form_error() {
+        let cli = AsyncRestClient::new("http://localhost", None).unwrap();
+        let err = cli
+            .request(Method::POST, "/users")
+            .form(&serde_json::json!({"nested": {"id": 1}}))
+            .send::<User>()
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind, FormDataError);
+    }
+
+    #[tokio::test]
+    async fn test_rest_request_serialize_error() {
+        let cli = AsyncRestClient::new("http://localhost", None).unwrap();
+        let mut data = BTreeMap::new();
+        data.insert((1, 2), "not a valid JSON key");
+        let err = cli
+            .request(Method::POST, "/users")
+            .json(&data)
+            .send::<User>()
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind, SyntaxError);
+    }
+
+    #[tokio::test]
+    async fn test_rest_request_empty_response() {
+        let server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(header("if-none-match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        let cli = AsyncRestClient::new(&server.uri(), None).unwrap().set_retry_delay(0);
+        cli.delete::<()>("/users/1".into(), None, None, None, None, None).await.unwrap();
+        let user = cli.request(Method::DELETE, "/users/1").send::<Option<User>>().await.unwrap();
+        assert_eq!(user, None);
+        let err = cli.request(Method::DELETE, "/users/1").send::<User>().await.unwrap_err();
+        assert_eq!(err.kind, CodecDataError);
+        let user = cli
+            .request(Method::GET, "/users/1")
+            .header(reqwest::header::IF_NONE_MATCH, HeaderValue::from_static("\"v1\""))
+            .send::<Option<User>>()
+            .await
+            .unwrap();
+        assert_eq!(user, None);
+        cli.request(Method::HEAD, "/users/1").send::<()>().await.unwrap();
+        assert_eq!(server.received_requests().await.unwrap().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_rest_request_patch() {
+        let server = MockServer::start().await;
+        Mock::given(method("PATCH"))
+            .and(header("content-type", "application/json-patch+json"))
+            .and(body_json(serde_json::json!([{"op": "replace", "path": "/name", "value": "Jane"}])))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"id": 1, "name": "Jane"})))
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(header("content-type", "application/merge-patch+json"))
+            .and(body_json(serde_json::json!({"name": "Jim", "nickname": null})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"id": 1, "name": "Jim"})))
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(header("content-type", "application/json"))
+            .and(body_json(serde_json::json!({"id": 1, "name": "Joe"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"id": 1, "name": "Joe"})))
+            .mount(&server)
+            .await;
+        let cli = AsyncRestClient::new(&server.uri(), None).unwrap();
+        let user: User = cli
+            .request(Method::PATCH, "/users/1")
+            .json_patch(&JsonPatch::new().replace("/name", "Jane"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(user.name, "Jane");
+        let user: User = cli
+            .request(Method::PATCH, "/users/1")
+            .merge_patch(&serde_json::json!({"name": "Jim", "nickname": null}))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(user.name, "Jim");
+        let data = User { id: 1, name: "Joe".into() };
+        let user: User = cli
+            .patch("/users/1".into(), None, Some(data), None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(user.name, "Joe");
+    }
+}
@@ -0,0 +1,346 @@
+/*!
+# Response Module
+
+This module provides [`HttpResponse`], the successful response returned by the `execute` methods of the
+clients and by [`HttpRequest::execute`](crate::HttpRequest::execute). Unlike the helpers returning the
+body as a `String`, it keeps the status, the headers, the final URL (after redirections), the number
+of attempts and the elapsed time of the request.
+
+The body is kept as raw bytes, so binary content (images, archives...) is returned unaltered; the text
+accessors decode it using the charset given by the `Content-Type` header, UTF-8 by default.
+
+## Examples
+
+```rust,no_run
+use cdumay_http_client::{ClientBuilder, RestClient};
+use reqwest::Method;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct User {
+    id: u64,
+}
+
+let client = RestClient::new("https://api.example.com", None).unwrap();
+let response = client.request(Method::GET, "/users/1").execute().unwrap();
+
+println!("{} after {} attempt(s) in {:?}", response.status(), response.attempts(), response.elapsed());
+if let Some(etag) = response.etag() {
+    println!("ETag: {}", etag);
+}
+let user: User = response.json().unwrap();
+```
+*/
+
+use bytes::Bytes;
+use cdumay_context::Context;
+use cdumay_error::Result;
+use encoding_rs::{Encoding, UTF_8};
+use mime::Mime;
+use reqwest::header::{AsHeaderName, HeaderMap, ALLOW, CONTENT_LENGTH, CONTENT_TYPE, ETAG, LOCATION};
+use reqwest::{Method, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use serde_value::Value;
+use std::borrow::Cow;
+use std::time::Duration;
+
+use crate::errors::rest::json_error_serialize;
+
+/// A successful (or `304 Not Modified`) HTTP response, with its body fully read.
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    method: Method,
+    status: StatusCode,
+    headers: HeaderMap,
+    url: Url,
+    attempts: u64,
+    elapsed: Duration,
+    body: Bytes,
+}
+
+impl HttpResponse {
+    /// Creates a response from its parts.
+    pub(crate) fn new(
+        method: Method,
+        status: StatusCode,
+        headers: HeaderMap,
+        url: Url,
+        attempts: u64,
+        elapsed: Duration,
+        body: Bytes,
+    ) -> HttpResponse {
+        HttpResponse {
+            method,
+            status,
+            headers,
+            url,
+            attempts,
+            elapsed,
+            body,
+        }
+    }
+
+    /// Returns the method of the request.
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    /// Returns the status code.
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Returns whether the status code is in the `2xx` range.
+    pub fn is_success(&self) -> bool {
+        self.status.is_success()
+    }
+
+    /// Returns whether the response has no content: responses to `HEAD` requests, `204 No Content`,
+    /// `205 Reset Content` and `304 Not Modified` responses, and empty bodies.
+    pub fn is_empty(&self) -> bool {
+        self.method == Method::HEAD
+            || matches!(
+                self.status,
+                StatusCode::NO_CONTENT | StatusCode::RESET_CONTENT | StatusCode::NOT_MODIFIED
+            )
+            || self.body.is_empty()
+    }
+
+    /// Returns the response headers.
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Returns the value of a header, if present and made of visible ASCII characters.
+    pub fn header<K: AsHeaderName>(&self, name: K) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+
+    /// Returns the `Content-Type` header.
+    pub fn content_type(&self) -> Option<&str> {
+        self.header(CONTENT_TYPE)
+    }
+
+    /// Returns the `Content-Length` header.
+    pub fn content_length(&self) -> Option<u64> {
+        self.header(CONTENT_LENGTH).and_then(|value| value.parse().ok())
+    }
+
+    /// Returns the `Location` header.
+    pub fn location(&self) -> Option<&str> {
+        self.header(LOCATION)
+    }
+
+    /// Returns the `ETag` header.
+    pub fn etag(&self) -> Option<&str> {
+        self.header(ETAG)
+    }
+
+    /// Returns the final URL of the response, after redirections.
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Returns the number of attempts made to get this response.
+    pub fn attempts(&self) -> u64 {
+        self.attempts
+    }
+
+    /// Returns the methods listed by the `Allow` header, sent with `OPTIONS` and
+    /// `405 Method Not Allowed` responses. Unknown or malformed names are skipped.
+    pub fn allowed_methods(&self) -> Vec<Method> {
+        self.headers
+            .get_all(ALLOW)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .filter_map(|name| Method::from_bytes(name.trim().as_bytes()).ok())
+            .collect()
+    }
+
+    /// Returns the time elapsed between the first attempt and the end of the body.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the encoding of the body, from the `charset` parameter of the `Content-Type` header.
+    ///
+    /// UTF-8 is returned when the header is missing or when its charset is unknown.
+    pub fn encoding(&self) -> &'static Encoding {
+        self.content_type()
+            .and_then(|value| value.parse::<Mime>().ok())
+            .and_then(|mime| {
+                mime.get_param(mime::CHARSET)
+                    .and_then(|charset| Encoding::for_label(charset.as_str().as_bytes()))
+            })
+            .unwrap_or(UTF_8)
+    }
+
+    /// Returns the body as text, decoded using [`encoding`](HttpResponse::encoding).
+    ///
+    /// Malformed sequences are replaced with `U+FFFD REPLACEMENT CHARACTER`.
+    pub fn text(&self) -> Cow<'_, str> {
+        self.encoding().decode(&self.body).0
+    }
+
+    /// Returns the raw body.
+    pub fn bytes(&self) -> &Bytes {
+        &self.body
+    }
+
+    /// Consumes the response and returns the raw body.
+    pub fn into_bytes(self) -> Bytes {
+        self.body
+    }
+
+    /// Consumes the response and returns the body as text.
+    pub fn into_text(self) -> String {
+        self.text().into_owned()
+    }
+
+    /// Deserializes the JSON body.
+    ///
+    /// A response without content is read as `null`, so that it can be deserialized into `()` or `None`.
+    pub fn json<R: DeserializeOwned>(&self) -> Result<R> {
+        let value = match self.is_empty() {
+            true => serde_json::from_value(serde_json::Value::Null),
+            false => serde_json::from_slice(&self.body),
+        };
+        value.map_err(|err| {
+            let mut context = Context::default();
+            context.insert("url".into(), Value::String(self.url.to_string()));
+            context.insert("status".into(), Value::U16(self.status.as_u16()));
+            json_error_serialize(err, Some(context))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use reqwest::{Method, StatusCode};
+    use serde::Deserialize;
+    use wiremock::matchers::{body_bytes, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use crate::{AsyncBaseClient, AsyncHttpClient, AsyncRestClient, ClientBuilder};
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct User {
+        id: u64,
+    }
+
+    #[tokio::test]
+    async fn test_response_parts() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/users"))
+            .respond_with(
+                ResponseTemplate::new(201)
+                    .insert_header("Location", "/users/1")
+                    .insert_header("ETag", "\"v1\"")
+                    .set_body_json(serde_json::json!({"id": 1})),
+            )
+            .mount(&server)
+            .await;
+        let cli = AsyncRestClient::new(&server.uri(), None).unwrap();
+        let response = cli.request(Method::POST, "/users").execute().await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert!(response.is_success());
+        assert_eq!(response.location(), Some("/users/1"));
+        assert_eq!(response.etag(), Some("\"v1\""));
+        assert_eq!(response.content_type(), Some("application/json"));
+        assert_eq!(response.url().as_str(), format!("{}/users", server.uri()));
+        assert_eq!(response.attempts(), 1);
+        assert_eq!(response.json::<User>().unwrap(), User { id: 1 });
+    }
+
+    #[tokio::test]
+    async fn test_response_no_content() {
+        let server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&server)
+            .await;
+        let cli = AsyncHttpClient::new(&server.uri(), None).unwrap();
+        let response = cli.request(Method::DELETE, "/users/1").execute().await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(response.text(), "");
+        assert!(response.is_empty());
+        assert_eq!(response.json::<Option<User>>().unwrap(), None);
+        response.json::<()>().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_response_head() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .respond_with(ResponseTemplate::new(200).insert_header("Content-Length", "42"))
+            .mount(&server)
+            .await;
+        let cli = AsyncHttpClient::new(&server.uri(), None).unwrap();
+        let response = cli.request(Method::HEAD, "/users/1").execute().await.unwrap();
+        assert_eq!(response.method(), Method::HEAD);
+        assert_eq!(response.content_length(), Some(42));
+        assert!(response.is_empty());
+        assert_eq!(response.json::<Option<User>>().unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_response_attempts() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .mount(&server)
+            .await;
+        let cli = AsyncHttpClient::new(&server.uri(), None)
+            .unwrap()
+            .set_retry_number(3)
+            .set_retry_delay(0);
+        let response = cli
+            .execute(Method::GET, "/".into(), None, None, None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(response.attempts(), 3);
+        assert_eq!(response.text(), "ok");
+        assert!(response.elapsed() > std::time::Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_response_binary() {
+        let body: Vec<u8> = vec![0x89, b'P', b'N', b'G', 0x00, 0xff, 0xfe];
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/images/1"))
+            .and(body_bytes(body.clone()))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body.clone(), "image/png"))
+            .mount(&server)
+            .await;
+        let cli = AsyncHttpClient::new(&server.uri(), None).unwrap();
+        let response = cli
+            .request(Method::PUT, "/images/1")
+            .body(body.clone())
+            .execute()
+            .await
+            .unwrap();
+        assert_eq!(response.bytes().as_ref(), body.as_slice());
+    }
+
+    #[tokio::test]
+    async fn test_response_charset() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(b"caf\xe9".to_vec(), "text/plain; charset=ISO-8859-1"))
+            .mount(&server)
+            .await;
+        let cli = AsyncHttpClient::new(&server.uri(), None).unwrap();
+        let response = cli.request(Method::GET, "/").execute().await.unwrap();
+        assert_eq!(response.encoding(), encoding_rs::WINDOWS_1252);
+        assert_eq!(response.text(), "café");
+        assert_eq!(response.into_text(), "café");
+    }
+}
@@ -0,0 +1,361 @@
+/*!
+# Retry Policy Module
+
+This module provides the policies deciding whether a failed request is retried and how long the client
+waits before the next attempt. A policy is attached to a client through
+[`ClientBuilder::set_retry_policy`](crate::ClientBuilder::set_retry_policy); the maximum number of
+attempts remains controlled by [`ClientBuilder::set_retry_number`](crate::ClientBuilder::set_retry_number).
+
+Built-in policies:
+
+- [`ConstantBackoff`]: always waits the same delay (the default, 30 seconds)
+- [`LinearBackoff`]: the delay grows by a fixed increment after each attempt
+- [`ExponentialBackoff`]: the delay is multiplied after each attempt
+- [`DecorrelatedJitter`]: a random delay between the base delay and three times the previous one
+
+Every built-in policy accepts a maximum elapsed time, after which no further attempt is made, and the
+growing ones a maximum delay capping each wait.
+
+## Examples
+
+```rust
+use std::time::Duration;
+use cdumay_http_client::{ClientBuilder, HttpClient};
+use cdumay_http_client::retry::ExponentialBackoff;
+
+let policy = ExponentialBackoff::new(Duration::from_millis(100))
+    .set_max_delay(Duration::from_secs(5))
+    .set_max_elapsed(Duration::from_secs(30));
+
+let client = HttpClient::new("https://api.example.com", None).unwrap()
+    .set_retry_number(8)
+    .set_retry_policy(policy);
+```
+
+### Custom Policy
+
+```rust
+use std::time::Duration;
+use cdumay_http_client::retry::{RetryPolicy, RetryState};
+
+/// Retries immediately once, then gives up.
+#[derive(Debug)]
+struct RetryOnce;
+
+impl RetryPolicy for RetryOnce {
+    fn next_delay(&self, state: &RetryState) -> Option<Duration> {
+        match state.attempt {
+            1 => Some(Duration::ZERO),
+            _ => None,
+        }
+    }
+}
+```
+*/
+
+use std::fmt::Debug;
+use std::time::Duration;
+
+use crate::utils::random_u64;
+
+/// State of a request passed to a [`RetryPolicy`] after a failed attempt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryState {
+    /// Number of the attempt which just failed, starting at 1.
+    pub attempt: u64,
+    /// Time elapsed since the first attempt started.
+    pub elapsed: Duration,
+    /// Delay waited before the failed attempt, `None` for the first one.
+    pub last_delay: Option<Duration>,
+}
+
+/// Decides whether a failed request is retried, and after which delay.
+///
+/// The trait requires `Send + Sync` so that clients holding a policy can be shared between threads.
+pub trait RetryPolicy: Debug + Send + Sync {
+    /// Returns the delay to wait before the next attempt, or `None` to stop retrying.
+    fn next_delay(&self, state: &RetryState) -> Option<Duration>;
+}
+
+/// Applies the maximum delay and the elapsed time budget to a computed delay.
+fn bounded(
+    delay: Duration,
+    max_delay: Option<Duration>,
+    max_elapsed: Option<Duration>,
+    state: &RetryState,
+) -> Option<Duration> {
+    let delay = match max_delay {
+        Some(max_delay) => delay.min(max_delay),
+        None => delay,
+    };
+    match max_elapsed {
+        Some(budget) if state.elapsed.saturating_add(delay) >= budget => None,
+        _ => Some(delay),
+    }
+}
+
+/// Returns a random number in `[0, 1)`.
+fn random_unit() -> f64 {
+    (random_u64() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Waits the same delay before each attempt.
+#[derive(Debug, Clone)]
+pub struct ConstantBackoff {
+    delay: Duration,
+    max_elapsed: Option<Duration>,
+}
+
+impl ConstantBackoff {
+    /// Creates a policy waiting `delay` before each attempt.
+    pub fn new(delay: Duration) -> ConstantBackoff {
+        ConstantBackoff {
+            delay,
+            max_elapsed: None,
+        }
+    }
+
+    /// Stops retrying once the next attempt would start after `max_elapsed`.
+    pub fn set_max_elapsed(mut self, max_elapsed: Duration) -> ConstantBackoff {
+        self.max_elapsed = Some(max_elapsed);
+        self
+    }
+}
+
+impl RetryPolicy for ConstantBackoff {
+    fn next_delay(&self, state: &RetryState) -> Option<Duration> {
+        bounded(self.delay, None, self.max_elapsed, state)
+    }
+}
+
+/// Increases the delay by a fixed increment after each attempt.
+#[derive(Debug, Clone)]
+pub struct LinearBackoff {
+    initial: Duration,
+    increment: Duration,
+    max_delay: Option<Duration>,
+    max_elapsed: Option<Duration>,
+}
+
+impl LinearBackoff {
+    /// Creates a policy waiting `initial`, then `initial + increment`, `initial + 2 * increment`...
+    pub fn new(initial: Duration, increment: Duration) -> LinearBackoff {
+        LinearBackoff {
+            initial,
+            increment,
+            max_delay: None,
+            max_elapsed: None,
+        }
+    }
+
+    /// Caps each delay to `max_delay`.
+    pub fn set_max_delay(mut self, max_delay: Duration) -> LinearBackoff {
+        self.max_delay = Some(max_delay);
+        self
+    }
+
+    /// Stops retrying once the next attempt would start after `max_elapsed`.
+    pub fn set_max_elapsed(mut self, max_elapsed: Duration) -> LinearBackoff {
+        self.max_elapsed = Some(max_elapsed);
+        self
+    }
+}
+
+impl RetryPolicy for LinearBackoff {
+    fn next_delay(&self, state: &RetryState) -> Option<Duration> {
+        let steps = u32::try_from(state.attempt.saturating_sub(1)).unwrap_or(u32::MAX);
+        let delay = self
+            .increment
+            .checked_mul(steps)
+            .and_then(|increment| self.initial.checked_add(increment))
+            .unwrap_or(Duration::MAX);
+        bounded(delay, self.max_delay, self.max_elapsed, state)
+    }
+}
+
+/// Multiplies the delay after each attempt.
+#[derive(Debug, Clone)]
+pub struct ExponentialBackoff {
+    initial: Duration,
+    multiplier: f64,
+    max_delay: Option<Duration>,
+    max_elapsed: Option<Duration>,
+}
+
+impl ExponentialBackoff {
+    /// Creates a policy waiting `initial`, then doubling the delay after each attempt.
+    pub fn new(initial: Duration) -> ExponentialBackoff {
+        ExponentialBackoff {
+            initial,
+            multiplier: 2.0,
+            max_delay: None,
+            max_elapsed: None,
+        }
+    }
+
+    /// Sets the factor applied to the delay after each attempt (default: 2).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `multiplier` is not a finite number greater than or equal to 1, as the delays would
+    /// otherwise shrink after each attempt or overflow.
+    pub fn set_multiplier(mut self, multiplier: f64) -> ExponentialBackoff {
+        if !multiplier.is_finite() || multiplier < 1.0 {
+            panic!("Multiplier MUST be a finite number >= 1 !");
+        }
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Caps each delay to `max_delay`.
+    pub fn set_max_delay(mut self, max_delay: Duration) -> ExponentialBackoff {
+        self.max_delay = Some(max_delay);
+        self
+    }
+
+    /// Stops retrying once the next attempt would start after `max_elapsed`.
+    pub fn set_max_elapsed(mut self, max_elapsed: Duration) -> ExponentialBackoff {
+        self.max_elapsed = Some(max_elapsed);
+        self
+    }
+}
+
+impl RetryPolicy for ExponentialBackoff {
+    fn next_delay(&self, state: &RetryState) -> Option<Duration> {
+        let exponent = i32::try_from(state.attempt.saturating_sub(1)).unwrap_or(i32::MAX);
+        let secs = self.initial.as_secs_f64() * self.multiplier.powi(exponent);
+        let delay = Duration::try_from_secs_f64(secs).unwrap_or(Duration::MAX);
+        bounded(delay, self.max_delay, self.max_elapsed, state)
+    }
+}
+
+/// Waits a random delay between the base delay and three times the previous delay.
+///
+/// This spreads the retries of concurrent clients over time, avoiding synchronized bursts against a
+/// recovering server.
+#[derive(Debug, Clone)]
+pub struct DecorrelatedJitter {
+    base: Duration,
+    max_delay: Option<Duration>,
+    max_elapsed: Option<Duration>,
+}
+
+impl DecorrelatedJitter {
+    /// Creates a policy whose delays are never shorter than `base`.
+    pub fn new(base: Duration) -> DecorrelatedJitter {
+        DecorrelatedJitter {
+            base,
+            max_delay: None,
+            max_elapsed: None,
+        }
+    }
+
+    /// Caps each delay to `max_delay`.
+    pub fn set_max_delay(mut self, max_delay: Duration) -> DecorrelatedJitter {
+        self.max_delay = Some(max_delay);
+        self
+    }
+
+    /// Stops retrying once the next attempt would start after `max_elapsed`.
+    pub fn set_max_elapsed(mut self, max_elapsed: Duration) -> DecorrelatedJitter {
+        self.max_elapsed = Some(max_elapsed);
+        self
+    }
+}
+
+impl RetryPolicy for DecorrelatedJitter {
+    fn next_delay(&self, state: &RetryState) -> Option<Duration> {
+        let upper = state
+            .last_delay
+            .unwrap_or(self.base)
+            .saturating_mul(3)
+            .max(self.base);
+        let delay = self.base + (upper - self.base).mul_f64(random_unit());
+        bounded(delay, self.max_delay, self.max_elapsed, state)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn state(attempt: u64, elapsed: u64, last_delay: Option<u64>) -> RetryState {
+        RetryState {
+            attempt,
+            elapsed: Duration::from_millis(elapsed),
+            last_delay: last_delay.map(Duration::from_millis),
+        }
+    }
+
+    #[test]
+    fn test_constant() {
+        let policy = ConstantBackoff::new(Duration::from_millis(250));
+        assert_eq!(policy.next_delay(&state(1, 0, None)), Some(Duration::from_millis(250)));
+        assert_eq!(policy.next_delay(&state(5, 0, Some(250))), Some(Duration::from_millis(250)));
+    }
+
+    #[test]
+    fn test_linear() {
+        let policy = LinearBackoff::new(Duration::from_millis(100), Duration::from_millis(50))
+            .set_max_delay(Duration::from_millis(180));
+        assert_eq!(policy.next_delay(&state(1, 0, None)), Some(Duration::from_millis(100)));
+        assert_eq!(policy.next_delay(&state(2, 0, None)), Some(Duration::from_millis(150)));
+        assert_eq!(policy.next_delay(&state(3, 0, None)), Some(Duration::from_millis(180)));
+    }
+
+    #[test]
+    fn test_exponential() {
+        let policy = ExponentialBackoff::new(Duration::from_millis(100))
+            .set_max_delay(Duration::from_secs(1));
+        assert_eq!(policy.next_delay(&state(1, 0, None)), Some(Duration::from_millis(100)));
+        assert_eq!(policy.next_delay(&state(2, 0, None)), Some(Duration::from_millis(200)));
+        assert_eq!(policy.next_delay(&state(4, 0, None)), Some(Duration::from_millis(800)));
+        assert_eq!(policy.next_delay(&state(5, 0, None)), Some(Duration::from_secs(1)));
+        assert_eq!(policy.next_delay(&state(u64::MAX, 0, None)), Some(Duration::from_secs(1)));
+
+        let policy = ExponentialBackoff::new(Duration::from_millis(100)).set_multiplier(1.0);
+        assert_eq!(policy.next_delay(&state(3, 0, None)), Some(Duration::from_millis(100)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_exponential_invalid_multiplier() {
+        let _ = ExponentialBackoff::new(Duration::from_millis(100)).set_multiplier(0.5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_exponential_nan_multiplier() {
+        let _ = ExponentialBackoff::new(Duration::from_millis(100)).set_multiplier(f64::NAN);
+    }
+
+    #[test]
+    fn test_decorrelated_jitter() {
+        let policy = DecorrelatedJitter::new(Duration::from_millis(100))
+            .set_max_delay(Duration::from_millis(500));
+        let mut last_delay = None;
+        for attempt in 1..50 {
+            let delay = policy
+                .next_delay(&RetryState {
+                    attempt,
+                    elapsed: Duration::ZERO,
+                    last_delay,
+                })
+                .unwrap();
+            let upper = last_delay.unwrap_or(Duration::from_millis(100)) * 3;
+            assert!(delay >= Duration::from_millis(100));
+            assert!(delay <= upper.min(Duration::from_millis(500)));
+            last_delay = Some(delay);
+        }
+    }
+
+    #[test]
+    fn test_max_elapsed() {
+        let policy = ConstantBackoff::new(Duration::from_millis(300))
+            .set_max_elapsed(Duration::from_secs(1));
+        assert_eq!(policy.next_delay(&state(1, 600, None)), Some(Duration::from_millis(300)));
+        assert_eq!(policy.next_delay(&state(2, 800, Some(300))), None);
+    }
+}
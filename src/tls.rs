@@ -0,0 +1,458 @@
+/*!
+# TLS Configuration Module
+
+This module provides the TLS settings shared by all the clients: custom root certificate authorities,
+built-in root certificates, minimum protocol version, hostname verification and client certificates. A [`TlsConfig`] is built
+once and attached to a client through [`ClientBuilder::set_tls_config`](crate::ClientBuilder::set_tls_config).
+
+## Examples
+
+### Custom Certificate Authority
+
+```rust,no_run
+use cdumay_http_client::{ClientBuilder, HttpClient};
+use cdumay_http_client::tls::{TlsConfig, Version};
+
+let tls = TlsConfig::new()
+    .add_root_certificate_file("/etc/ssl/private-ca.pem").unwrap()
+    .set_built_in_root_certs(false)      // Only trust the private CA
+    .set_min_tls_version(Version::TLS_1_2);
+
+let client = HttpClient::new("https://internal.example.com", None).unwrap()
+    .set_tls_config(tls);
+```
+
+### Client Certificate (Mutual TLS)
+
+A client identity can be loaded from a PEM certificate chain and its PKCS#8 private key, or from a
+PKCS#12 archive. It is then presented to every server requesting a client certificate:
+
+```rust,no_run
+use cdumay_http_client::{ClientBuilder, HttpClient};
+use cdumay_http_client::tls::TlsConfig;
+
+let tls = TlsConfig::new()
+    .set_identity_pem_file("/etc/ssl/client.pem", "/etc/ssl/client.key").unwrap();
+
+let client = HttpClient::new("https://internal.example.com", None).unwrap()
+    .set_tls_config(tls);
+```
+
+### Disabling Verification
+
+Certificate verification can be disabled altogether with
+[`ClientBuilder::set_ssl_verify`](crate::ClientBuilder::set_ssl_verify), or restricted to the hostname check:
+
+```rust
+use cdumay_http_client::{ClientBuilder, HttpClient};
+use cdumay_http_client::tls::TlsConfig;
+
+let client = HttpClient::new("https://10.0.0.1", None).unwrap()
+    .set_tls_config(TlsConfig::new().set_hostname_verify(false));
+```
+
+Both settings make the connection vulnerable to man-in-the-middle attacks and should only be used in
+controlled environments.
+*/
+
+use cdumay_context::Context;
+use cdumay_error::Result;
+use serde_value::Value;
+use std::fs;
+use std::path::Path;
+
+use crate::errors::client::ClientBuilderError;
+
+pub use reqwest::tls::{Certificate, Identity, Version};
+
+/// TLS settings applied to every connection opened by a client.
+///
+/// By default, the built-in root certificates are trusted, hostnames are verified and the minimum
+/// protocol version is left to the TLS backend.
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    root_certificates: Vec<Certificate>,
+    built_in_root_certs: bool,
+    min_tls_version: Option<Version>,
+    hostname_verify: bool,
+    identity: Option<Identity>,
+}
+
+impl Default for TlsConfig {
+    fn default() -> Self {
+        TlsConfig {
+            root_certificates: Vec::new(),
+            built_in_root_certs: true,
+            min_tls_version: None,
+            hostname_verify: true,
+            identity: None,
+        }
+    }
+}
+
+/// Applies a [`TlsConfig`] to a `reqwest` client builder.
+///
+/// The blocking and asynchronous builders expose the same TLS methods without sharing a trait.
+macro_rules! apply_tls {
+    ($config:expr, $builder:expr, $ssl_verify:expr) => {{
+        let config = $config;
+        let mut builder = $builder
+            .danger_accept_invalid_certs(!$ssl_verify)
+            .danger_accept_invalid_hostnames(!config.hostname_verify)
+            .tls_built_in_root_certs(config.built_in_root_certs);
+        for cert in &config.root_certificates {
+            builder = builder.add_root_certificate(cert.clone());
+        }
+        if let Some(version) = config.min_tls_version {
+            builder = builder.min_tls_version(version);
+        }
+        if let Some(identity) = &config.identity {
+            builder = builder.identity(identity.clone());
+        }
+        builder
+    }};
+}
+
+impl TlsConfig {
+    /// Creates a new TLS configuration with default settings.
+    pub fn new() -> TlsConfig {
+        TlsConfig::default()
+    }
+
+    /// Adds a trusted root certificate.
+    pub fn add_root_certificate(mut self, cert: Certificate) -> TlsConfig {
+        self.root_certificates.push(cert);
+        self
+    }
+
+    /// Adds all the root certificates found in a PEM bundle.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Result<TlsConfig>` which is:
+    /// - `Ok(TlsConfig)` if the bundle was parsed successfully
+    /// - `Err` with a `ClientBuilderError` if the bundle is not valid PEM
+    pub fn add_root_certificate_pem(mut self, pem: &[u8]) -> Result<TlsConfig> {
+        let certs = Certificate::from_pem_bundle(pem).map_err(|err| {
+            ClientBuilderError::new().set_message(format!("Failed to parse root certificates: {}", err))
+        })?;
+        self.root_certificates.extend(certs);
+        Ok(self)
+    }
+
+    /// Adds all the root certificates found in a PEM file.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Result<TlsConfig>` which is:
+    /// - `Ok(TlsConfig)` if the file was read and parsed successfully
+    /// - `Err` with a `ClientBuilderError` holding the file path if it cannot be read or parsed
+    pub fn add_root_certificate_file<P: AsRef<Path>>(self, path: P) -> Result<TlsConfig> {
+        let mut context = Context::new();
+        context.insert("path".into(), Value::String(path.as_ref().display().to_string()));
+        let pem = read_file(path.as_ref(), &context)?;
+        self.add_root_certificate_pem(&pem).map_err(|mut err| {
+            err.details = Some(context.into());
+            err
+        })
+    }
+
+    /// Enables or disables the built-in root certificates of the TLS backend.
+    ///
+    /// Disabling them is useful to trust only the certificates added with
+    /// [`add_root_certificate`](TlsConfig::add_root_certificate).
+    pub fn set_built_in_root_certs(mut self, enabled: bool) -> TlsConfig {
+        self.built_in_root_certs = enabled;
+        self
+    }
+
+    /// Sets the minimum TLS version accepted for connections.
+    ///
+    /// Note that the default `native-tls` backend cannot enforce TLS 1.3 as a minimum: requests then
+    /// fail with a `ClientBuilderError`.
+    pub fn set_min_tls_version(mut self, version: Version) -> TlsConfig {
+        self.min_tls_version = Some(version);
+        self
+    }
+
+    /// Enables or disables the verification of the server hostname against its certificate.
+    pub fn set_hostname_verify(mut self, enabled: bool) -> TlsConfig {
+        self.hostname_verify = enabled;
+        self
+    }
+
+    /// Sets the client identity presented to servers requesting a client certificate.
+    pub fn set_identity(mut self, identity: Identity) -> TlsConfig {
+        self.identity = Some(identity);
+        self
+    }
+
+    /// Sets the client identity from a PEM certificate chain and a PEM encoded PKCS#8 private key.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Result<TlsConfig>` which is:
+    /// - `Ok(TlsConfig)` if the identity was parsed successfully
+    /// - `Err` with a `ClientBuilderError` if the certificate or the key cannot be parsed
+    pub fn set_identity_pem(self, cert: &[u8], key: &[u8]) -> Result<TlsConfig> {
+        let identity = Identity::from_pkcs8_pem(cert, key).map_err(|err| {
+            ClientBuilderError::new().set_message(format!("Failed to parse client identity: {}", err))
+        })?;
+        Ok(self.set_identity(identity))
+    }
+
+    /// Sets the client identity from a DER encoded PKCS#12 archive protected by `password`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Result<TlsConfig>` which is:
+    /// - `Ok(TlsConfig)` if the archive was parsed successfully
+    /// - `Err` with a `ClientBuilderError` if the archive cannot be parsed or decrypted
+    pub fn set_identity_pkcs12(self, der: &[u8], password: &str) -> Result<TlsConfig> {
+        let identity = Identity::from_pkcs12_der(der, password).map_err(|err| {
+            ClientBuilderError::new().set_message(format!("Failed to parse client identity: {}", err))
+        })?;
+        Ok(self.set_identity(identity))
+    }
+
+    /// Sets the client identity from a PEM certificate file and a PKCS#8 private key file.
+    ///
+    /// Errors hold the paths of both files in their details.
+    pub fn set_identity_pem_file<C: AsRef<Path>, K: AsRef<Path>>(
+        self,
+        cert_path: C,
+        key_path: K,
+    ) -> Result<TlsConfig> {
+        let mut context = Context::new();
+        context.insert("cert_path".into(), Value::String(cert_path.as_ref().display().to_string()));
+        context.insert("key_path".into(), Value::String(key_path.as_ref().display().to_string()));
+        let cert = read_file(cert_path.as_ref(), &context)?;
+        let key = read_file(key_path.as_ref(), &context)?;
+        self.set_identity_pem(&cert, &key).map_err(|mut err| {
+            err.details = Some(context.into());
+            err
+        })
+    }
+
+    /// Sets the client identity from a PKCS#12 file protected by `password`.
+    ///
+    /// Errors hold the path of the file in their details.
+    pub fn set_identity_pkcs12_file<P: AsRef<Path>>(self, path: P, password: &str) -> Result<TlsConfig> {
+        let mut context = Context::new();
+        context.insert("path".into(), Value::String(path.as_ref().display().to_string()));
+        let der = read_file(path.as_ref(), &context)?;
+        self.set_identity_pkcs12(&der, password).map_err(|mut err| {
+            err.details = Some(context.into());
+            err
+        })
+    }
+
+    /// Returns the custom root certificates.
+    pub fn root_certificates(&self) -> &[Certificate] {
+        &self.root_certificates
+    }
+
+    /// Returns whether the built-in root certificates are trusted.
+    pub fn built_in_root_certs(&self) -> bool {
+        self.built_in_root_certs
+    }
+
+    /// Returns the minimum TLS version, if any.
+    pub fn min_tls_version(&self) -> Option<Version> {
+        self.min_tls_version
+    }
+
+    /// Returns whether hostnames are verified.
+    pub fn hostname_verify(&self) -> bool {
+        self.hostname_verify
+    }
+
+    /// Returns the client identity, if any.
+    pub fn identity(&self) -> Option<&Identity> {
+        self.identity.as_ref()
+    }
+
+    /// Applies the settings to a blocking `reqwest` client builder.
+    pub(crate) fn apply(&self, builder: reqwest::blocking::ClientBuilder, ssl_verify: bool) -> reqwest::blocking::ClientBuilder {
+        apply_tls!(self, builder, ssl_verify)
+    }
+
+    /// Applies the settings to an asynchronous `reqwest` client builder.
+    pub(crate) fn apply_async(&self, builder: reqwest::ClientBuilder, ssl_verify: bool) -> reqwest::ClientBuilder {
+        apply_tls!(self, builder, ssl_verify)
+    }
+}
+
+/// Reads a certificate or key file, reporting failures as a `ClientBuilderError`.
+fn read_file(path: &Path, context: &Context) -> Result<Vec<u8>> {
+    fs::read(path).map_err(|err| {
+        ClientBuilderError::new()
+            .set_message(format!("Failed to read {}: {}", path.display(), err))
+            .set_details(context.clone().into())
+            .into()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+    use std::thread;
+
+    use openssl::ssl::{SslAcceptor, SslFiletype, SslMethod, SslVerifyMode, SslVersion};
+
+    use crate::errors::client::{GENERIC_HTTP_CLIENT_ERROR, NETWORK_CONNECTION};
+    use crate::tls::{TlsConfig, Version};
+    use crate::{ClientBuilder, HttpClient};
+
+    const CA: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/ca.pem");
+    const SERVER_CERT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/server.pem");
+    const SERVER_KEY: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/server.key");
+    const CLIENT_CERT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/client.pem");
+    const CLIENT_KEY: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/client.key");
+    const CLIENT_PKCS12: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/client.p12");
+
+    /// Starts a local HTTPS server answering `ok` to every request and returns its port.
+    fn serve(max_version: Option<SslVersion>) -> u16 {
+        let mut acceptor = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls()).unwrap();
+        acceptor.set_private_key_file(SERVER_KEY, SslFiletype::PEM).unwrap();
+        acceptor.set_certificate_chain_file(SERVER_CERT).unwrap();
+        acceptor.set_max_proto_version(max_version).unwrap();
+        listen(acceptor.build())
+    }
+
+    /// Starts a local HTTPS server which requires a client certificate signed by the test CA.
+    fn serve_mutual() -> u16 {
+        let mut acceptor = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls()).unwrap();
+        acceptor.set_private_key_file(SERVER_KEY, SslFiletype::PEM).unwrap();
+        acceptor.set_certificate_chain_file(SERVER_CERT).unwrap();
+        acceptor.set_ca_file(CA).unwrap();
+        acceptor.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+        listen(acceptor.build())
+    }
+
+    fn listen(acceptor: SslAcceptor) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                if let Ok(mut stream) = acceptor.accept(stream) {
+                    let mut buf = [0; 4096];
+                    let _ = stream.read(&mut buf);
+                    let _ = stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok");
+                }
+            }
+        });
+        port
+    }
+
+    fn client(host: &str, port: u16) -> HttpClient {
+        HttpClient::new(&format!("https://{}:{}", host, port), None)
+            .unwrap()
+            .set_retry_number(1)
+            .set_retry_delay(0)
+    }
+
+    #[test]
+    fn test_self_signed_rejected() {
+        let port = serve(None);
+        let result = client("localhost", port).get("/".into(), None, None, None, None, None);
+        assert_eq!(result.unwrap_err().kind, NETWORK_CONNECTION);
+    }
+
+    #[test]
+    fn test_ssl_verify_disabled() {
+        let port = serve(None);
+        let result = client("localhost", port)
+            .set_ssl_verify(false)
+            .get("/".into(), None, None, None, None, None);
+        assert_eq!(result.unwrap(), "ok");
+    }
+
+    #[test]
+    fn test_custom_root_certificate() {
+        let port = serve(None);
+        let tls = TlsConfig::new().add_root_certificate_file(CA).unwrap().set_built_in_root_certs(false);
+        let result = client("localhost", port)
+            .set_tls_config(tls)
+            .get("/".into(), None, None, None, None, None);
+        assert_eq!(result.unwrap(), "ok");
+    }
+
+    #[test]
+    fn test_hostname_verify() {
+        let port = serve(None);
+        let tls = TlsConfig::new().add_root_certificate_file(CA).unwrap();
+        let result = client("127.0.0.1", port)
+            .set_tls_config(tls.clone())
+            .get("/".into(), None, None, None, None, None);
+        assert_eq!(result.unwrap_err().kind, NETWORK_CONNECTION);
+
+        let result = client("127.0.0.1", port)
+            .set_tls_config(tls.set_hostname_verify(false))
+            .get("/".into(), None, None, None, None, None);
+        assert_eq!(result.unwrap(), "ok");
+    }
+
+    #[test]
+    fn test_min_tls_version() {
+        let port = serve(Some(SslVersion::TLS1_2));
+        let tls = TlsConfig::new().add_root_certificate_file(CA).unwrap();
+        let result = client("localhost", port)
+            .set_tls_config(tls.clone().set_min_tls_version(Version::TLS_1_2))
+            .get("/".into(), None, None, None, None, None);
+        assert_eq!(result.unwrap(), "ok");
+
+        let result = client("localhost", port)
+            .set_tls_config(tls.set_min_tls_version(Version::TLS_1_3))
+            .get("/".into(), None, None, None, None, None);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_client_identity_pem() {
+        let port = serve_mutual();
+        let tls = TlsConfig::new().add_root_certificate_file(CA).unwrap();
+        let result = client("localhost", port)
+            .set_tls_config(tls.clone())
+            .get("/".into(), None, None, None, None, None);
+        assert!(result.is_err());
+
+        let result = client("localhost", port)
+            .set_tls_config(tls.set_identity_pem_file(CLIENT_CERT, PathBuf::from(CLIENT_KEY)).unwrap())
+            .get("/".into(), None, None, None, None, None);
+        assert_eq!(result.unwrap(), "ok");
+    }
+
+    #[test]
+    fn test_client_identity_pkcs12() {
+        let port = serve_mutual();
+        let tls = TlsConfig::new()
+            .add_root_certificate_file(CA)
+            .unwrap()
+            .set_identity_pkcs12_file(CLIENT_PKCS12, "password")
+            .unwrap();
+        let result = client("localhost", port)
+            .set_tls_config(tls)
+            .get("/".into(), None, None, None, None, None);
+        assert_eq!(result.unwrap(), "ok");
+    }
+
+    #[test]
+    fn test_invalid_client_identity() {
+        let err = TlsConfig::new().set_identity_pem(b"not a certificate", b"not a key").unwrap_err();
+        assert_eq!(err.kind, GENERIC_HTTP_CLIENT_ERROR);
+
+        let err = TlsConfig::new().set_identity_pkcs12_file(CLIENT_PKCS12, "wrong").unwrap_err();
+        assert_eq!(err.kind, GENERIC_HTTP_CLIENT_ERROR);
+        assert!(err.details.unwrap().contains_key("path"));
+    }
+
+    #[test]
+    fn test_invalid_root_certificate() {
+        let err = TlsConfig::new().add_root_certificate_file("/nonexistent/ca.pem").unwrap_err();
+        assert_eq!(err.kind, GENERIC_HTTP_CLIENT_ERROR);
+        assert!(err.details.unwrap().contains_key("path"));
+    }
+}
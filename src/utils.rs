@@ -71,11 +71,24 @@ assert_eq!(
 ```
 */
 
-use crate::errors::client::InvalidUrl;
-use cdumay_error::Result;
-use reqwest::header::HeaderMap;
-use reqwest::Url;
+use crate::authentication::Authentication;
+use crate::errors::client::{ClientBuilderError, InvalidUrl, NETWORK_CONNECTION};
+use crate::retry::{RetryPolicy, RetryState};
+use cdumay_context::Context;
+use cdumay_error::{Error, ErrorKind, Result};
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, RETRY_AFTER};
+use reqwest::{Method, StatusCode, Url};
+use serde_value::Value;
+use std::collections::hash_map::RandomState;
 use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Header carrying the key which lets a server deduplicate retried non-idempotent requests.
+pub(crate) const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
 
 /// Merges two sets of HTTP headers.
 ///
@@ -185,3 +198,343 @@ pub fn build_url(root: &Url, path: String, params: Option<HashMap<String, String
     }
     Ok(url)
 }
+
+/// Returns the value stored in `cell`, initializing it with `init` on first use.
+///
+/// This is a stable stand-in for `OnceLock::get_or_try_init`: when `init` fails, the cell is left
+/// empty so that the next call tries again.
+pub(crate) fn get_or_try_init<T: Clone, F: FnOnce() -> Result<T>>(cell: &OnceLock<T>, init: F) -> Result<T> {
+    if let Some(value) = cell.get() {
+        return Ok(value.clone());
+    }
+    let value = init()?;
+    Ok(cell.get_or_init(|| value).clone())
+}
+
+/// Returns the delay requested by the `Retry-After` header of a `429 Too Many Requests` or
+/// `503 Service Unavailable` response.
+///
+/// The header value may be given either in seconds or as an HTTP-date; a date in the past gives a zero
+/// delay. `None` is returned for other statuses and for missing or malformed headers.
+pub(crate) fn retry_after(status: StatusCode, headers: &HeaderMap) -> Option<Duration> {
+    if status != StatusCode::TOO_MANY_REQUESTS && status != StatusCode::SERVICE_UNAVAILABLE {
+        return None;
+    }
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some((date.with_timezone(&Utc) - Utc::now()).to_std().unwrap_or_default())
+}
+
+/// Returns a random number, unique for each call within the process.
+pub(crate) fn random_u64() -> u64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let mut hasher = RandomState::new().build_hasher();
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    hasher.write_u128(nanos);
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    hasher.finish()
+}
+
+/// Returns whether a request using `method` can safely be sent several times.
+pub(crate) fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS | Method::TRACE
+    ) || matches!(
+        // The idempotent WebDAV methods (RFC 4918).
+        method.as_str(),
+        "PROPFIND" | "PROPPATCH" | "MKCOL" | "COPY" | "MOVE" | "UNLOCK"
+    )
+}
+
+/// Returns the headers and the query parameters added by `auth` to an attempt of a request to `url`.
+pub(crate) fn authentication(
+    auth: Option<&dyn Authentication>,
+    method: &Method,
+    url: &Url,
+) -> (HeaderMap, Vec<(String, String)>) {
+    match auth {
+        Some(auth) => {
+            let query = auth.as_query();
+            let mut url = url.clone();
+            if !query.is_empty() {
+                url.query_pairs_mut().extend_pairs(&query);
+            }
+            (auth.authorize(method, &url).into_iter().collect(), query)
+        }
+        None => (HeaderMap::new(), Vec::new()),
+    }
+}
+
+/// Returns `url` without the query parameters added by `auth`, so that credentials sent in the query
+/// string are not exposed by logs, error details and responses.
+///
+/// Only the pairs appended by `auth` are removed, matched on their name and value as they were encoded;
+/// the other parameters keep their original encoding.
+pub(crate) fn strip_auth_query(url: &Url, auth: Option<&dyn Authentication>) -> Url {
+    let mut url = url.clone();
+    let Some(query) = url.query() else { return url };
+    let mut added: Vec<String> = auth
+        .map(|auth| auth.as_query())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|pair| serde_urlencoded::to_string([pair]).ok())
+        .collect();
+    if added.is_empty() {
+        return url;
+    }
+    let mut pairs: Vec<&str> = query.split('&').collect();
+    // The pairs of `auth` are appended after the parameters of the request.
+    for index in (0..pairs.len()).rev() {
+        if let Some(position) = added.iter().position(|pair| pair == pairs[index]) {
+            added.swap_remove(position);
+            pairs.remove(index);
+        }
+    }
+    let query = pairs.join("&");
+    url.set_query(Some(query.as_str()).filter(|query| !query.is_empty()));
+    url
+}
+
+/// Generates a new `Idempotency-Key` value, formatted as a random (version 4) UUID.
+pub(crate) fn idempotency_key() -> HeaderValue {
+    let high = (random_u64() & 0xffff_ffff_ffff_0fff) | 0x0000_0000_0000_4000;
+    let low = (random_u64() & 0x3fff_ffff_ffff_ffff) | 0x8000_0000_0000_0000;
+    let key = format!(
+        "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+        high >> 32,
+        (high >> 16) & 0xffff,
+        high & 0xffff,
+        low >> 48,
+        low & 0xffff_ffff_ffff
+    );
+    HeaderValue::from_str(&key).expect("an hexadecimal UUID is a valid header value")
+}
+
+/// Returns the headers of a request: the client headers, overridden by the request headers, plus an
+/// `Idempotency-Key` for `POST` and `PATCH` requests when `auto_idempotency_key` is enabled.
+pub(crate) fn request_headers(
+    client_headers: &HeaderMap,
+    headers: Option<HeaderMap>,
+    method: &Method,
+    auto_idempotency_key: bool,
+) -> HeaderMap {
+    let mut headers = merge_headers(client_headers, headers);
+    if auto_idempotency_key
+        && (method == Method::POST || method == Method::PATCH)
+        && !headers.contains_key(IDEMPOTENCY_KEY)
+    {
+        headers.insert(IDEMPOTENCY_KEY, idempotency_key());
+    }
+    headers
+}
+
+/// Returns how many times a request may be sent: requests using non-idempotent methods are sent only
+/// once, unless `retry_non_idempotent` is enabled or they carry an `Idempotency-Key` header.
+pub(crate) fn retry_number(method: &Method, headers: &HeaderMap, retry_non_idempotent: bool, retry_number: u64) -> u64 {
+    match is_idempotent(method) || retry_non_idempotent || headers.contains_key(IDEMPOTENCY_KEY) {
+        true => retry_number,
+        false => 1,
+    }
+}
+
+/// How an attempt answered by the server must be handled, as decided by [`Attempts::response`].
+pub(crate) enum Outcome {
+    /// The response is returned to the caller.
+    Success,
+    /// The credentials were refreshed after a `401 Unauthorized`: the request is sent again.
+    Reauthenticate,
+    /// The response is turned into an error, then passed to [`Attempts::failed`].
+    Failure,
+}
+
+/// Bookkeeping of the attempts of a request, shared by the blocking and asynchronous clients.
+///
+/// The clients only send the attempts and sleep between them; this type logs them, builds their error
+/// context, handles `401 Unauthorized` responses and decides whether and when a failed attempt is
+/// retried.
+pub(crate) struct Attempts<'a> {
+    method: Method,
+    url: Url,
+    context: Context,
+    start: DateTime<Utc>,
+    retry_number: u64,
+    no_retry_on: Option<Vec<ErrorKind>>,
+    policy: &'a dyn RetryPolicy,
+    max_retry_after: u64,
+    tries: u64,
+    reauthenticated: bool,
+    rejected: bool,
+    server_delay: Option<Duration>,
+    last_delay: Option<Duration>,
+}
+
+impl<'a> Attempts<'a> {
+    /// Starts the attempts of a request to `url`, sent at most `retry_number` times.
+    pub(crate) fn new(
+        method: &Method,
+        url: &Url,
+        context: Option<Context>,
+        no_retry_on: Option<Vec<ErrorKind>>,
+        retry_number: u64,
+        policy: &'a dyn RetryPolicy,
+        max_retry_after: u64,
+    ) -> Attempts<'a> {
+        let mut context = context.unwrap_or_default();
+        context.insert("url".into(), Value::String(url.to_string()));
+        context.insert("method".into(), Value::String(method.to_string()));
+        debug!("{} {}", method, url.as_str());
+        Attempts {
+            method: method.clone(),
+            url: url.clone(),
+            context,
+            start: Utc::now(),
+            retry_number,
+            no_retry_on,
+            policy,
+            max_retry_after,
+            tries: 0,
+            reauthenticated: false,
+            rejected: false,
+            server_delay: None,
+            last_delay: None,
+        }
+    }
+
+    /// Starts a new attempt and returns the context reported by its errors.
+    pub(crate) fn next(&mut self) -> Context {
+        self.tries += 1;
+        self.server_delay = None;
+        info!("[{}] - {} (try: {})", self.method, self.url, self.tries);
+        let mut context = self.context.clone();
+        context.insert("try".into(), Value::U64(self.tries));
+        context
+    }
+
+    /// Returns the number of requests sent, including the one sent again after a credentials refresh.
+    pub(crate) fn count(&self) -> u64 {
+        self.tries + u64::from(self.reauthenticated)
+    }
+
+    /// Returns the error raised when a request cannot be cloned for another attempt.
+    pub(crate) fn clone_error(&self) -> Error {
+        ClientBuilderError::new()
+            .set_message("Internal error, failed to clone request".into())
+            .set_details(self.context.clone().into())
+            .into()
+    }
+
+    /// Logs the response of the current attempt and tells how to handle it.
+    ///
+    /// A `304 Not Modified` response, answering a conditional request, is handled like a successful
+    /// one. After a first `401 Unauthorized` response, the request is sent again without counting as a
+    /// retry when [`Authentication::on_unauthorized`] refreshed the credentials; a second one is
+    /// returned without further retry, as the refreshed credentials were rejected too.
+    pub(crate) fn response(
+        &mut self,
+        status: StatusCode,
+        headers: &HeaderMap,
+        length: Option<u64>,
+        auth: Option<&dyn Authentication>,
+    ) -> Outcome {
+        let elapsed = { Utc::now() - self.start }.to_std().unwrap_or_default();
+        let human = humantime::format_duration(elapsed).to_string();
+        let length = length.unwrap_or(0);
+        if status.is_success() || status == StatusCode::NOT_MODIFIED {
+            info!("{} {} - {} - {} [{}]", self.method, self.url.as_str(), status, length, human);
+            return Outcome::Success;
+        }
+        error!("{} {} - {} - {} [{}]", self.method, self.url.as_str(), status, length, human);
+        if status == StatusCode::UNAUTHORIZED && self.reauthenticated {
+            self.rejected = true;
+        } else if status == StatusCode::UNAUTHORIZED
+            && auth.map(|auth| auth.on_unauthorized(headers)).unwrap_or(false)
+        {
+            info!("[{}] - {} (retry with refreshed credentials)", self.method, self.url);
+            self.reauthenticated = true;
+            self.tries -= 1;
+            return Outcome::Reauthenticate;
+        }
+        self.server_delay = retry_after(status, headers);
+        Outcome::Failure
+    }
+
+    /// Checks the transport error of the current attempt: network failures are passed to
+    /// [`failed`](Attempts::failed), other errors are returned at once.
+    pub(crate) fn transport_error(&self, err: Error) -> Result<Error> {
+        match err.kind == NETWORK_CONNECTION {
+            true => {
+                error!("{} {} - {}", self.method, self.url.as_str(), err);
+                Ok(err)
+            }
+            false => Err(err),
+        }
+    }
+
+    /// Handles the failure of the current attempt, returning either the delay to wait before the next
+    /// attempt or the error to return to the caller.
+    ///
+    /// The delay is given by the retry policy, unless the server requested one with a `Retry-After`
+    /// header.
+    pub(crate) fn failed(&mut self, err: Error) -> Result<Duration> {
+        if let Some(kinds) = &self.no_retry_on {
+            if kinds.contains(&err.kind) {
+                return Err(err);
+            }
+        }
+        let delay = match self.tries < self.retry_number && !self.rejected {
+            true => self.policy.next_delay(&RetryState {
+                attempt: self.tries,
+                elapsed: { Utc::now() - self.start }.to_std().unwrap_or_default(),
+                last_delay: self.last_delay,
+            }),
+            false => None,
+        };
+        match delay {
+            Some(delay) => {
+                let delay = match self.server_delay {
+                    Some(server_delay) => server_delay.min(Duration::from_secs(self.max_retry_after)),
+                    None => delay,
+                };
+                debug!("[{}] - {} (retry in {})", self.method, self.url, humantime::format_duration(delay));
+                self.last_delay = Some(delay);
+                Ok(delay)
+            }
+            None => {
+                error!(
+                    "Failed to perform request {} on {} after {} retries : {}",
+                    self.method, self.url, self.tries, err
+                );
+                Err(err)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use reqwest::Url;
+
+    use super::strip_auth_query;
+    use crate::authentication::api_key::ApiKeyAuth;
+
+    #[test]
+    fn test_strip_auth_query() {
+        let auth = ApiKeyAuth::query("api_key", "a secret");
+        let strip = |url: &str| strip_auth_query(&Url::parse(url).unwrap(), Some(&auth)).to_string();
+        assert_eq!(
+            strip("https://api.example.com/users?q=a%20b&api_key=mine&api_key=a+secret"),
+            "https://api.example.com/users?q=a%20b&api_key=mine"
+        );
+        assert_eq!(strip("https://api.example.com/users?api_key=a+secret"), "https://api.example.com/users");
+        assert_eq!(strip("https://api.example.com/users?q=1"), "https://api.example.com/users?q=1");
+        let url = Url::parse("https://api.example.com/users?api_key=a+secret").unwrap();
+        assert_eq!(strip_auth_query(&url, None), url);
+    }
+}